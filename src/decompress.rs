@@ -0,0 +1,54 @@
+use std::io::Read;
+
+use crate::mime::Mime;
+
+// The memory limit of the xz and lzip decoders.
+const MEM_LIMIT: u64 = 64 << 20;
+
+// payload decompresses the leading portion, at most max bytes, of a gzip, bzip2,
+// xz, zstd or lzip stream. Returns None if mime is not one of these formats, or
+// if nothing could be decompressed.
+pub(crate) fn payload(mime: &Mime, content: &[u8], max: usize) -> Option<Vec<u8>> {
+    let decoder: Box<dyn Read + '_> = if mime.mime == "application/gzip" {
+        Box::new(flate2::read::MultiGzDecoder::new(content))
+    } else if mime.mime == "application/x-bzip2" {
+        Box::new(bzip2::read::MultiBzDecoder::new(content))
+    } else if mime.mime == "application/x-xz" {
+        Box::new(xz2::read::XzDecoder::new_multi_decoder(content))
+    } else if mime.mime == "application/zstd" {
+        Box::new(zstd::stream::read::Decoder::new(content).ok()?)
+    } else if mime.mime == "application/lzip" {
+        lzip_decoder(content)?
+    } else {
+        return None;
+    };
+
+    // Truncated streams end with an error, after the bytes decompressed so far.
+    let mut buf = Vec::new();
+    let _ = decoder.take(max as u64).read_to_end(&mut buf);
+    (!buf.is_empty()).then_some(buf)
+}
+
+// lzip_decoder decodes the LZMA data of an lzip member by prepending it with the
+// header of the legacy .lzma format, which the lzip header translates to.
+// https://www.nongnu.org/lzip/manual/lzip_manual.html#File-format
+fn lzip_decoder(content: &[u8]) -> Option<Box<dyn Read + '_>> {
+    if content.len() < 6 || content[4] != 1 {
+        return None;
+    }
+    // The dictionary size is a power of 2 minus 0 to 7 sixteenths of it.
+    let base = 1u32.checked_shl((content[5] & 0x1F) as u32)?;
+    let dict_size = base - base / 16 * (content[5] >> 5) as u32;
+
+    // lc=3, lp=0 and pb=2 properties, the dictionary size and an unknown
+    // uncompressed size.
+    let mut header = vec![0x5D];
+    header.extend_from_slice(&dict_size.to_le_bytes());
+    header.extend_from_slice(&u64::MAX.to_le_bytes());
+
+    let stream = xz2::stream::Stream::new_lzma_decoder(MEM_LIMIT).ok()?;
+    Some(Box::new(xz2::read::XzDecoder::new_stream(
+        std::io::Cursor::new(header).chain(&content[6..]),
+        stream,
+    )))
+}
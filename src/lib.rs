@@ -1,5 +1,15 @@
+#[cfg(feature = "decompress")]
+mod decompress;
 mod magic;
+mod members;
 pub mod mime;
 mod tree;
 
-pub use mime::{detech_from_reader, detect, set_rate_limit};
+pub use mime::{
+    archive_members, contains_private_key, detech_from_reader, detect, detect_csv_dialect,
+    detect_disk_image, detect_source_language, image_info, set_rate_limit, set_source_threshold,
+    ArchiveLimits, ArchiveMember, CsvDialect, Language, LineTerminator,
+};
+
+#[cfg(feature = "decompress")]
+pub use mime::detect_nested;
@@ -71,7 +71,7 @@ fn tar_chksum(b: &[u8]) -> (i64, i64) {
 }
 
 // tarParseOctal converts octal string to decimal int.
-fn tar_parse_octal(bs: &[u8]) -> i64 {
+pub(crate) fn tar_parse_octal(bs: &[u8]) -> i64 {
     // Because unused fields are filled with NULs, we need to skip leading NULs.
     // Fields may also be padded with spaces or NULs.
     // So we remove leading and trailing NULs and spaces to be sure.
@@ -415,20 +415,253 @@ impl ZipTokenizer {
 
 // zipContains returns true if the zip file headers from in contain any of the paths.
 pub(crate) fn zip_contains(content: &[u8], paths: Vec<&[u8]>) -> bool {
+    zip_any(content, |name| paths.iter().any(|p| name.starts_with(p)))
+}
+
+// zip_any returns true if any of the file names from the zip headers matches f.
+pub(crate) fn zip_any(content: &[u8], f: impl Fn(&[u8]) -> bool) -> bool {
     let mut tokenizer = ZipTokenizer {
         content: content.to_vec(),
         index: 0,
     };
     while let Some(token) = tokenizer.next() {
-        for p in paths.clone() {
-            if token.starts_with(p) {
-                return true;
-            }
+        if f(token) {
+            return true;
         }
     }
     false
 }
 
+// ZipContains matches a zip archive containing a file whose name starts with
+// any of the paths.
+pub(crate) struct ZipContainsDetector {
+    pub paths: Vec<&'static [u8]>,
+}
+
+impl MimeDetector for ZipContainsDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        zip_contains(content, self.paths.clone())
+    }
+}
+
+// Aar matches an Android library archive: an Android manifest along with the
+// compiled classes or the resource symbols of the library.
+pub(crate) struct AarDetector;
+
+impl MimeDetector for AarDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        zip_contains(content, vec![b"AndroidManifest.xml"])
+            && zip_any(content, |name| name == b"classes.jar" || name == b"R.txt")
+    }
+}
+
+// Nupkg matches a NuGet package, which holds its .nuspec manifest at the root.
+pub(crate) struct NupkgDetector;
+
+impl MimeDetector for NupkgDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        zip_any(content, |name| {
+            name.ends_with(b".nuspec") && !name.contains(&b'/')
+        })
+    }
+}
+
+// Wheel matches a Python wheel, which holds its metadata in a .dist-info directory.
+pub(crate) struct WheelDetector;
+
+impl MimeDetector for WheelDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        zip_any(content, |name| name.index(b".dist-info/").is_some())
+    }
+}
+
+// Kmz matches a zipped KML file, usually named doc.kml.
+pub(crate) struct KmzDetector;
+
+impl MimeDetector for KmzDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        zip_any(content, |name| {
+            name.ends_with(b".kml") && !name.contains(&b'/')
+        })
+    }
+}
+
+// Usdz matches a USDZ package: an uncompressed zip whose first file is a USD scene.
+pub(crate) struct UsdzDetector;
+
+impl MimeDetector for UsdzDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        let mut tokenizer = ZipTokenizer {
+            content: content.to_vec(),
+            index: 0,
+        };
+        tokenizer
+            .next()
+            .is_some_and(|name| name.ends_with(b".usdc") || name.ends_with(b".usda"))
+    }
+}
+
+// Sketch matches a Sketch design file.
+pub(crate) struct SketchDetector;
+
+impl MimeDetector for SketchDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        zip_any(content, |name| name == b"document.json")
+            && zip_contains(content, vec![b"pages/", b"meta.json"])
+    }
+}
+
+// zip_stored_member returns the data, possibly cut short by the end of content,
+// of the first member named name when it is stored without compression.
+fn zip_stored_member<'a>(content: &'a [u8], name: &[u8]) -> Option<&'a [u8]> {
+    let mut i = 0;
+    while let Some(pk) = content[i..].index(b"PK\x03\x04") {
+        let header = content.get(i + pk..i + pk + 30)?;
+        let name_len = LittleEndian::read_u16(&header[26..28]) as usize;
+        let extra_len = LittleEndian::read_u16(&header[28..30]) as usize;
+        let start = i + pk + 30;
+        if content.get(start..start + name_len)? == name {
+            if LittleEndian::read_u16(&header[8..10]) != 0 {
+                return None;
+            }
+            let size = LittleEndian::read_u32(&header[18..22]) as usize;
+            let data = content.get(start + name_len + extra_len..)?;
+            return Some(&data[..size.min(data.len())]);
+        }
+        i = start + name_len;
+    }
+    None
+}
+
+// varint reads a protocol buffers base 128 varint, returning it along with the
+// rest of data.
+fn varint(data: &[u8]) -> Option<(u64, &[u8])> {
+    let mut v = 0u64;
+    for (i, &b) in data.iter().take(10).enumerate() {
+        v |= ((b & 0x7F) as u64) << (7 * i);
+        if b & 0x80 == 0 {
+            return Some((v, &data[i + 1..]));
+        }
+    }
+    None
+}
+
+// iwork_document_type returns the message type of the root object of an iWork
+// document, the first archive of Index/Document.iwa. IWA files are made of
+// Snappy compressed chunks, each with a 4 bytes header; a Snappy stream starts
+// with a literal, which holds the length prefixed ArchiveInfo message of the
+// object: its identifier in field 1, and its message type in field 1 of the
+// MessageInfo in field 2.
+// https://github.com/obriensp/iWorkFileFormat/blob/master/Docs/index.md
+fn iwork_document_type(content: &[u8]) -> Option<u64> {
+    let iwa = zip_stored_member(content, b"Index/Document.iwa")?;
+    if iwa.first() != Some(&0) {
+        return None;
+    }
+    let (_, snappy) = varint(iwa.get(4..)?)?;
+    let (&tag, rest) = snappy.split_first()?;
+    if tag & 0x03 != 0 {
+        return None;
+    }
+    // Literals of 60 bytes or more store their length in the next 1 to 4 bytes.
+    let literal = match (tag >> 2) as usize {
+        n @ 0..=59 => rest.get(..n + 1)?,
+        n => {
+            let len_bytes = n - 59;
+            let len = LittleEndian::read_uint(rest.get(..len_bytes)?, len_bytes) as usize;
+            let rest = &rest[len_bytes..];
+            &rest[..(len + 1).min(rest.len())]
+        }
+    };
+    let (len, info) = varint(literal)?;
+    let mut info = info.get(..len as usize)?;
+    let mut identifier = None;
+    while let Some((key, rest)) = varint(info) {
+        match key {
+            0x08 => {
+                let (id, rest) = varint(rest)?;
+                identifier = Some(id);
+                info = rest;
+            }
+            0x12 => {
+                let (len, rest) = varint(rest)?;
+                let message_info = rest.get(..len as usize)?;
+                let (0x08, message_info) = varint(message_info)? else {
+                    return None;
+                };
+                return identifier
+                    .filter(|&id| id == 1)
+                    .and_then(|_| varint(message_info))
+                    .map(|(t, _)| t);
+            }
+            _ => return None,
+        }
+    }
+    None
+}
+
+// iwork_app returns the application, "keynote", "numbers" or "pages", an iWork
+// document was made with.
+fn iwork_app(content: &[u8]) -> Option<&'static str> {
+    // Keynote '09 presentations.
+    if zip_any(content, |name| name == b"index.apxl") {
+        return Some("keynote");
+    }
+    if !zip_any(content, |name| name == b"Index/Document.iwa") {
+        return None;
+    }
+    // Only presentations have slides, while all three applications may embed
+    // tables.
+    if zip_contains(content, vec![b"Index/Slide", b"Index/MasterSlide"]) {
+        return Some("keynote");
+    }
+    // The root object is a TP.DocumentArchive in Pages documents, and a
+    // TN.DocumentArchive, of type 1, in Numbers spreadsheets.
+    match iwork_document_type(content) {
+        Some(10000) => Some("pages"),
+        Some(1) => Some("numbers"),
+        // A spreadsheet has at least one table.
+        _ if zip_contains(content, vec![b"Index/Tables/"]) => Some("numbers"),
+        _ => Some("pages"),
+    }
+}
+
+// Iwork matches a Keynote, Numbers or Pages document, told apart by the
+// application.
+pub(crate) struct IworkDetector {
+    pub app: &'static str,
+}
+
+impl MimeDetector for IworkDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        iwork_app(content) == Some(self.app)
+    }
+}
+
+// Cbz matches a comic book archive: a zip of images, with optional ComicInfo.xml
+// metadata.
+pub(crate) struct CbzDetector;
+
+impl MimeDetector for CbzDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        const IMAGES: &[&[u8]] = &[b".jpg", b".jpeg", b".png", b".gif", b".webp", b".avif"];
+        let mut tokenizer = ZipTokenizer {
+            content: content.to_vec(),
+            index: 0,
+        };
+        let mut images = 0;
+        while let Some(name) = tokenizer.next() {
+            let lower = name.to_ascii_lowercase();
+            if IMAGES.iter().any(|ext| lower.ends_with(ext)) {
+                images += 1;
+            } else if name != b"ComicInfo.xml" && !name.ends_with(b"/") {
+                return false;
+            }
+        }
+        images > 0
+    }
+}
+
 // CRX matches a Chrome extension file: a zip archive prepended by a package header.
 pub(crate) struct CrxDetector;
 
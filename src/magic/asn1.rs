@@ -0,0 +1,321 @@
+use crate::mime::MimeDetector;
+
+const INTEGER: u8 = 0x02;
+const OCTET_STRING: u8 = 0x04;
+const OID: u8 = 0x06;
+const UTC_TIME: u8 = 0x17;
+const GENERALIZED_TIME: u8 = 0x18;
+const SEQUENCE: u8 = 0x30;
+const SET: u8 = 0x31;
+// The [0] EXPLICIT context specific tag.
+const CONTEXT_0: u8 = 0xA0;
+
+// 1.2.840.113549.1.7, the PKCS#7 content types.
+const OID_PKCS7: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x07];
+// 1.2.840.113549.1.7.1, PKCS#7 data.
+const OID_PKCS7_DATA: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x07, 0x01];
+// 1.2.840.113549.1.7.2, PKCS#7 signedData.
+const OID_PKCS7_SIGNED_DATA: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x07, 0x02];
+// 1.2.840.113549.1.9.16.1.4, the TSTInfo content type of timestamp tokens.
+const OID_TST_INFO: &[u8] = &[
+    0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x09, 0x10, 0x01, 0x04,
+];
+// 1.2.840.113549.1.5, the PKCS#5 password based encryption schemes.
+const OID_PKCS5: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x05];
+// 1.2.840.113549.1.12.1, the PKCS#12 password based encryption schemes.
+const OID_PKCS12_PBE: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x0C, 0x01];
+
+// Tlv is a DER encoded value: its tag and its content octets. The content is
+// cut short when the input is truncated.
+#[derive(Clone, Copy)]
+pub(crate) struct Tlv<'a> {
+    pub tag: u8,
+    pub value: &'a [u8],
+}
+
+impl<'a> Tlv<'a> {
+    // children reads the values nested in a constructed value.
+    pub(crate) fn children(&self) -> Der<'a> {
+        Der::new(self.value)
+    }
+
+    // small_int returns the value of an INTEGER encoded in a single byte.
+    fn small_int(&self) -> Option<u8> {
+        match (self.tag, self.value) {
+            (INTEGER, [v]) => Some(*v),
+            _ => None,
+        }
+    }
+
+    fn is_oid(&self, oid: &[u8]) -> bool {
+        self.tag == OID && self.value == oid
+    }
+}
+
+// Der reads a sequence of DER (or BER, with indefinite lengths) encoded values.
+// It never looks further than the values it is asked for, so detectors walking
+// a few levels deep stay bounded whatever the input.
+pub(crate) struct Der<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Der<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Der { data }
+    }
+
+    // tags returns the tags of the next n values, fewer if the data ends.
+    fn tags(self, n: usize) -> Vec<u8> {
+        self.take(n).map(|v| v.tag).collect()
+    }
+}
+
+impl<'a> Iterator for Der<'a> {
+    type Item = Tlv<'a>;
+
+    fn next(&mut self) -> Option<Tlv<'a>> {
+        let (&tag, rest) = self.data.split_first()?;
+        // High tag numbers are not used by the formats below.
+        if tag & 0x1F == 0x1F {
+            self.data = &[];
+            return None;
+        }
+        let (&first, rest) = rest.split_first()?;
+        let (len, rest) = match first {
+            0..=0x7F => (first as usize, rest),
+            // The indefinite length form is only valid for constructed values,
+            // which then run up to the end of their parent.
+            0x80 if tag & 0x20 != 0 => (rest.len(), rest),
+            0x81..=0x84 => {
+                let n = (first & 0x7F) as usize;
+                let bytes = rest.get(..n)?;
+                let len = bytes.iter().fold(0usize, |l, &b| l << 8 | b as usize);
+                (len, &rest[n..])
+            }
+            _ => {
+                self.data = &[];
+                return None;
+            }
+        };
+        let len = len.min(rest.len());
+        self.data = &rest[len..];
+        Some(Tlv {
+            tag,
+            value: &rest[..len],
+        })
+    }
+}
+
+// outer_sequence returns the value of the outermost SEQUENCE of content.
+fn outer_sequence(content: &[u8]) -> Option<Tlv<'_>> {
+    Der::new(content).next().filter(|v| v.tag == SEQUENCE)
+}
+
+// content_info returns the content type OID of a PKCS#7 ContentInfo, along
+// with the [0] EXPLICIT content.
+// ContentInfo ::= SEQUENCE { contentType OID, content [0] EXPLICIT ANY OPTIONAL }
+fn content_info(tlv: Tlv<'_>) -> Option<(&[u8], Option<Tlv<'_>>)> {
+    if tlv.tag != SEQUENCE {
+        return None;
+    }
+    let mut der = tlv.children();
+    let oid = der.next().filter(|v| v.tag == OID)?;
+    Some((oid.value, der.next().filter(|v| v.tag == CONTEXT_0)))
+}
+
+// is_pkcs7 checks for a DER encoded PKCS#7 (CMS) ContentInfo of any content type.
+pub(crate) fn is_pkcs7(content: &[u8]) -> bool {
+    Der::new(content)
+        .next()
+        .and_then(content_info)
+        .is_some_and(|(oid, _)| oid.len() == OID_PKCS7.len() + 1 && oid.starts_with(OID_PKCS7))
+}
+
+// TimestampToken matches an RFC 3161 timestamp token: a signedData whose
+// encapsulated content is a TSTInfo.
+pub(crate) struct TimestampTokenDetector;
+
+impl MimeDetector for TimestampTokenDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        Der::new(content).next().is_some_and(is_timestamp_token)
+    }
+}
+
+// SignedData ::= SEQUENCE { version INTEGER, digestAlgorithms SET,
+//                           encapContentInfo SEQUENCE { eContentType OID, ... }, ... }
+fn is_timestamp_token(tlv: Tlv<'_>) -> bool {
+    let Some((oid, Some(content))) = content_info(tlv) else {
+        return false;
+    };
+    if oid != OID_PKCS7_SIGNED_DATA {
+        return false;
+    }
+    let Some(signed_data) = content.children().next().filter(|v| v.tag == SEQUENCE) else {
+        return false;
+    };
+    let mut der = signed_data.children();
+    let (Some(version), Some(digests), Some(encap)) = (der.next(), der.next(), der.next()) else {
+        return false;
+    };
+    version.tag == INTEGER
+        && digests.tag == SET
+        && encap.tag == SEQUENCE
+        && encap
+            .children()
+            .next()
+            .is_some_and(|v| v.is_oid(OID_TST_INFO))
+}
+
+// X509Certificate matches a DER encoded X.509 certificate.
+// TBSCertificate ::= SEQUENCE { version [0] EXPLICIT OPTIONAL, serialNumber INTEGER,
+//                               signature AlgorithmIdentifier, issuer Name, validity Validity, ... }
+pub(crate) struct X509CertificateDetector;
+
+impl MimeDetector for X509CertificateDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        let Some(tbs) = outer_sequence(content).and_then(|v| v.children().next()) else {
+            return false;
+        };
+        if tbs.tag != SEQUENCE {
+            return false;
+        }
+        let mut tags = tbs.children().tags(5);
+        if tags.first() == Some(&CONTEXT_0) {
+            tags.remove(0);
+        }
+        tags.starts_with(&[INTEGER, SEQUENCE, SEQUENCE, SEQUENCE])
+    }
+}
+
+// X509Crl matches a DER encoded X.509 certificate revocation list.
+// TBSCertList ::= SEQUENCE { version INTEGER OPTIONAL, signature AlgorithmIdentifier,
+//                            issuer Name, thisUpdate Time, ... }
+pub(crate) struct X509CrlDetector;
+
+impl MimeDetector for X509CrlDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        let Some(tbs) = outer_sequence(content).and_then(|v| v.children().next()) else {
+            return false;
+        };
+        if tbs.tag != SEQUENCE {
+            return false;
+        }
+        let mut tags = tbs.children().tags(4);
+        if tags.first() == Some(&INTEGER) {
+            tags.remove(0);
+        }
+        tags.len() >= 3
+            && tags[..2] == [SEQUENCE, SEQUENCE]
+            && (tags[2] == UTC_TIME || tags[2] == GENERALIZED_TIME)
+    }
+}
+
+// Pkcs8 matches a DER encoded PKCS#8 private key.
+// PrivateKeyInfo ::= SEQUENCE { version INTEGER, privateKeyAlgorithm AlgorithmIdentifier,
+//                               privateKey OCTET STRING, ... }
+pub(crate) struct Pkcs8Detector;
+
+impl MimeDetector for Pkcs8Detector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        let Some(info) = outer_sequence(content) else {
+            return false;
+        };
+        let mut der = info.children();
+        let (Some(version), Some(algorithm), Some(key)) = (der.next(), der.next(), der.next())
+        else {
+            return false;
+        };
+        // Version 1 is the OneAsymmetricKey of RFC 5958.
+        matches!(version.small_int(), Some(0 | 1))
+            && algorithm.tag == SEQUENCE
+            && algorithm.children().next().is_some_and(|v| v.tag == OID)
+            && key.tag == OCTET_STRING
+    }
+}
+
+// Pkcs8Encrypted matches a DER encoded PKCS#8 encrypted private key.
+// EncryptedPrivateKeyInfo ::= SEQUENCE { encryptionAlgorithm AlgorithmIdentifier,
+//                                        encryptedData OCTET STRING }
+pub(crate) struct Pkcs8EncryptedDetector;
+
+impl MimeDetector for Pkcs8EncryptedDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        let Some(info) = outer_sequence(content) else {
+            return false;
+        };
+        let mut der = info.children();
+        let (Some(algorithm), Some(data)) = (der.next(), der.next()) else {
+            return false;
+        };
+        algorithm.tag == SEQUENCE
+            && data.tag == OCTET_STRING
+            && algorithm.children().next().is_some_and(|v| {
+                v.tag == OID
+                    && (v.value.starts_with(OID_PKCS5) || v.value.starts_with(OID_PKCS12_PBE))
+            })
+    }
+}
+
+// Pkcs12 matches a PKCS#12 (PFX) file.
+// PFX ::= SEQUENCE { version INTEGER {v3(3)}, authSafe ContentInfo, macData MacData OPTIONAL }
+pub(crate) struct Pkcs12Detector;
+
+impl MimeDetector for Pkcs12Detector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        let Some(pfx) = outer_sequence(content) else {
+            return false;
+        };
+        let mut der = pfx.children();
+        let (Some(version), Some(auth_safe)) = (der.next(), der.next()) else {
+            return false;
+        };
+        version.small_int() == Some(3)
+            && content_info(auth_safe)
+                .is_some_and(|(oid, _)| oid == OID_PKCS7_DATA || oid == OID_PKCS7_SIGNED_DATA)
+    }
+}
+
+// TimestampQuery matches an RFC 3161 timestamp request.
+// TimeStampReq ::= SEQUENCE { version INTEGER {v1(1)},
+//                             messageImprint SEQUENCE { hashAlgorithm AlgorithmIdentifier,
+//                                                       hashedMessage OCTET STRING }, ... }
+pub(crate) struct TimestampQueryDetector;
+
+impl MimeDetector for TimestampQueryDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        let Some(req) = outer_sequence(content) else {
+            return false;
+        };
+        let mut der = req.children();
+        let (Some(version), Some(imprint)) = (der.next(), der.next()) else {
+            return false;
+        };
+        version.small_int() == Some(1)
+            && imprint.tag == SEQUENCE
+            && imprint.children().tags(2) == [SEQUENCE, OCTET_STRING]
+    }
+}
+
+// TimestampReply matches an RFC 3161 timestamp response holding a token.
+// TimeStampResp ::= SEQUENCE { status PKIStatusInfo, timeStampToken ContentInfo OPTIONAL }
+pub(crate) struct TimestampReplyDetector;
+
+impl MimeDetector for TimestampReplyDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        let Some(resp) = outer_sequence(content) else {
+            return false;
+        };
+        let mut der = resp.children();
+        let (Some(status), Some(token)) = (der.next(), der.next()) else {
+            return false;
+        };
+        // PKIStatus values range from granted(0) to revocationNotification(5).
+        status.tag == SEQUENCE
+            && status
+                .children()
+                .next()
+                .and_then(|v| v.small_int())
+                .is_some_and(|s| s <= 5)
+            && is_timestamp_token(token)
+    }
+}
@@ -2,8 +2,6 @@ use byteorder::{BigEndian, ByteOrder, LittleEndian, ReadBytesExt};
 
 use crate::mime::MimeDetector;
 
-use super::base::BytesExt;
-
 pub(crate) struct Mp3Detector;
 
 impl MimeDetector for Mp3Detector {
@@ -30,23 +28,6 @@ impl MimeDetector for Mp3Detector {
     }
 }
 
-pub(crate) struct WavDetector;
-
-impl MimeDetector for WavDetector {
-    fn detect(&self, content: &[u8], _: usize) -> bool {
-        if content.len() < 12 {
-            return false;
-        }
-        if &content[..4] != b"RIFF" {
-            return false;
-        }
-        if content[8..12] != [0x57, 0x41, 0x56, 0x45] {
-            return false;
-        }
-        true
-    }
-}
-
 pub(crate) struct AiffDetector;
 
 impl MimeDetector for AiffDetector {
@@ -64,21 +45,78 @@ impl MimeDetector for AiffDetector {
     }
 }
 
-pub(crate) struct QcpDetector;
+// OggStream is the kind of codec of an Ogg logical stream.
+#[derive(PartialEq)]
+enum OggStream {
+    Audio(&'static str),
+    Video(&'static str),
+    // An Ogg Skeleton stream, holding metadata about the other streams.
+    Skeleton,
+}
+
+// ogg_stream identifies the codec of an Ogg logical stream from its first
+// packet, the codec identification header.
+// https://www.rfc-editor.org/rfc/rfc5334.html
+fn ogg_stream(packet: &[u8]) -> Option<OggStream> {
+    let codecs: [(&[u8], OggStream); 8] = [
+        (b"\x01vorbis", OggStream::Audio("vorbis")),
+        (b"OpusHead", OggStream::Audio("opus")),
+        (b"Speex   ", OggStream::Audio("speex")),
+        (b"\x7fFLAC", OggStream::Audio("flac")),
+        (b"\x80theora", OggStream::Video("theora")),
+        (b"BBCD\x00", OggStream::Video("dirac")),
+        // OGM video streams, which do not have a standard codec name.
+        (b"\x01video\x00\x00\x00", OggStream::Video("")),
+        (b"fishead\x00", OggStream::Skeleton),
+    ];
+    codecs
+        .into_iter()
+        .find(|(sig, _)| packet.starts_with(sig))
+        .map(|(_, s)| s)
+}
 
-impl MimeDetector for QcpDetector {
-    fn detect(&self, content: &[u8], _: usize) -> bool {
-        if content.len() < 4 {
-            return false;
-        }
-        if &content[..4] != b"RIFF" {
-            return false;
-        }
-        if &content[8..12] != b"QLCM" {
-            return false;
-        }
-        true
+// ogg_streams identifies the logical streams of an Ogg file. Each stream
+// starts with a page flagged as beginning of stream, all of which come before
+// any other page of the file.
+fn ogg_streams(content: &[u8]) -> Vec<OggStream> {
+    let (mut streams, mut data) = (vec![], content);
+    // The page header is 27 bytes, followed by the segment table.
+    while data.len() >= 27 && data.starts_with(b"OggS\x00") && data[5] & 0x02 != 0 {
+        let segments = data[26] as usize;
+        let Some(table) = data.get(27..27 + segments) else {
+            break;
+        };
+        // The first packet spans segments up to the first one shorter than 255.
+        let packet_len: usize = table
+            .iter()
+            .position(|&l| l < 255)
+            .map_or(table.iter().map(|&l| l as usize).sum(), |i| {
+                table[..=i].iter().map(|&l| l as usize).sum()
+            });
+        let body = &data[27 + segments..];
+        match ogg_stream(&body[..packet_len.min(body.len())]) {
+            Some(s) => streams.push(s),
+            None => break,
+        }
+        let page_len: usize = table.iter().map(|&l| l as usize).sum();
+        data = &body[page_len.min(body.len())..];
     }
+    streams
+}
+
+// ogg_params reports the codecs of the audio and video streams of an Ogg file.
+pub(crate) fn ogg_params(content: &[u8]) -> Vec<(&'static str, String)> {
+    let codecs: Vec<&str> = ogg_streams(content)
+        .into_iter()
+        .filter_map(|s| match s {
+            OggStream::Audio(c) | OggStream::Video(c) if !c.is_empty() => Some(c),
+            _ => None,
+        })
+        .collect();
+    if codecs.is_empty() {
+        return vec![];
+    }
+    vec![("codecs", codecs.join(", "))]
 }
 
 // OggAudio matches an audio ogg file.
@@ -86,23 +124,33 @@ pub(crate) struct OggAudioDetector;
 
 impl MimeDetector for OggAudioDetector {
     fn detect(&self, content: &[u8], _: usize) -> bool {
-        content.len() >= 37
-            && (content[28..].starts_with(b"\x7fFLAC")
-                || content[28..].starts_with(b"\x01vorbis")
-                || content[28..].starts_with(b"OpusHead")
-                || content[28..].starts_with(b"Speex\x20\x20\x20"))
+        let streams = ogg_streams(content);
+        streams.iter().any(|s| matches!(s, OggStream::Audio(_)))
+            && !streams.iter().any(|s| matches!(s, OggStream::Video(_)))
+    }
+}
+
+// OggOpus matches an Ogg file holding only Opus audio streams.
+pub(crate) struct OggOpusDetector;
+
+impl MimeDetector for OggOpusDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        let streams = ogg_streams(content);
+        streams.contains(&OggStream::Audio("opus"))
+            && streams
+                .iter()
+                .all(|s| *s == OggStream::Audio("opus") || *s == OggStream::Skeleton)
     }
 }
 
-// OggVideo matches a video ogg file.
+// OggVideo matches a video ogg file. A file of which only the Skeleton stream
+// is known is assumed to be a video.
 pub(crate) struct OggVideoDetector;
 
 impl MimeDetector for OggVideoDetector {
     fn detect(&self, content: &[u8], _: usize) -> bool {
-        content.len() >= 37
-            && (content[28..].starts_with(b"\x80theora")
-                || content[28..].starts_with(b"fishead\x00")
-                || content[28..].starts_with(b"\x01video\x00\x00\x00"))
+        let streams = ogg_streams(content);
+        streams.iter().any(|s| matches!(s, OggStream::Video(_))) || streams == [OggStream::Skeleton]
     }
 }
 
@@ -118,78 +166,6 @@ impl MimeDetector for MpegDetector {
     }
 }
 
-// WebM matches a WebM file.
-pub(crate) struct WebMDetector;
-
-impl MimeDetector for WebMDetector {
-    fn detect(&self, content: &[u8], _: usize) -> bool {
-        is_matroska_file_type_matched(content, "webm".to_string())
-    }
-}
-
-// Mkv matches a mkv file.
-pub(crate) struct MkvDetector;
-
-impl MimeDetector for MkvDetector {
-    fn detect(&self, content: &[u8], _: usize) -> bool {
-        is_matroska_file_type_matched(content, "matroska".to_string())
-    }
-}
-
-// isMatroskaFileTypeMatched is used for webm and mkv file matching.
-// It checks for .Eß£ sequence. If the sequence is found,
-// then it means it is Matroska media container, including WebM.
-// Then it verifies which of the file type it is representing by matching the
-// file specific string.
-fn is_matroska_file_type_matched(content: &[u8], fl_type: String) -> bool {
-    if content.starts_with(b"\x1A\x45\xDF\xA3") {
-        return is_file_type_name_present(content, fl_type);
-    }
-    false
-}
-
-fn is_file_type_name_present(content: &[u8], fl_type: String) -> bool {
-    let (mut max_ind, len_in) = (4096, content.len());
-    if len_in < max_ind {
-        // restricting length to 4096
-        max_ind = len_in
-    }
-    let Some(i) = content[..max_ind].index(b"\x42\x82") else {
-        return false;
-    };
-    let mut ind = i;
-    if ind > 0 && len_in > ind + 2 {
-        ind += 2;
-
-        // filetype name will be present exactly
-        // n bytes after the match of the two bytes "\x42\x82"
-        let n = vint_width(content[ind] as usize);
-        if len_in > ind + n {
-            return content[ind + n..].starts_with(fl_type.as_bytes());
-        }
-    }
-    false
-}
-
-// vintWidth parses the variable-integer width in matroska containers
-fn vint_width(v: usize) -> usize {
-    let (mut mask, max, mut num) = (128, 8, 1);
-    while num < max && v & mask == 0 {
-        mask >>= 1;
-        num += 1;
-    }
-    num
-}
-
-// Avi matches an Audio Video Interleaved file.
-pub(crate) struct AviDetector;
-
-impl MimeDetector for AviDetector {
-    fn detect(&self, content: &[u8], _: usize) -> bool {
-        content.len() > 16 && &content[..4] == b"RIFF" && &content[8..16] == b"AVI LIST"
-    }
-}
-
 // Shp matches a shape format file.
 // https://www.esri.com/library/whitepapers/pdfs/shapefile.pdf
 pub(crate) struct ShpDetector;
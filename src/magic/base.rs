@@ -50,7 +50,7 @@ fn ci_check(sig: &&[u8], content: &&[u8]) -> bool {
     }
     for (i, b) in sig.iter().enumerate() {
         let mut db = content[i];
-        if db.is_ascii_uppercase() {
+        if b.is_ascii_uppercase() {
             db &= 0xDF;
         }
         if b != &db {
@@ -75,14 +75,12 @@ impl MimeDetector for CiPrefixDetector {
     }
 }
 
-#[allow(unused)]
 #[derive(Debug, Clone, Default)]
 pub(crate) struct XmlSig {
     local_name: &'static [u8],
     xmlns: &'static [u8],
 }
 
-#[allow(unused)]
 impl XmlSig {
     pub(crate) fn new(local_name: &'static str, xmlns: &'static str) -> Self {
         let mut ret = XmlSig {
@@ -97,7 +95,6 @@ impl XmlSig {
     }
 }
 
-#[allow(unused)]
 fn xml_check(sig: &XmlSig, content: &[u8]) -> bool {
     let content = &content[..content.len().min(512)];
     if sig.local_name.is_empty() {
@@ -123,7 +120,6 @@ fn xml_check(sig: &XmlSig, content: &[u8]) -> bool {
     false
 }
 
-#[allow(unused)]
 pub(crate) struct XmlDetector {
     pub sigs: Vec<XmlSig>,
 }
@@ -165,7 +161,18 @@ pub(crate) fn trim_right_ws(content: &[u8]) -> &[u8] {
     &content[..end]
 }
 
-fn first_line(input: &[u8]) -> &[u8] {
+// dropLastLine drops the last incomplete line from content.
+pub(crate) fn drop_last_line(content: &[u8], limit: usize) -> &[u8] {
+    if limit == 0 || content.len() < limit {
+        return content;
+    }
+    match content.iter().rposition(|&b| b == b'\n' || b == b'\r') {
+        Some(i) => &content[..i + 1],
+        None => content,
+    }
+}
+
+pub(crate) fn first_line(input: &[u8]) -> &[u8] {
     if let Some(pos) = input.iter().position(|&b| b == b'\n') {
         &input[..pos] // 返回从开头到换行符之前的字节
     } else {
@@ -239,27 +246,56 @@ impl MimeDetector for FtypDetector {
     }
 }
 
-fn shebang_check(sig: &[u8], content: &[u8]) -> bool {
-    if content.len() < sig.len() + 2 {
-        return false;
+// shebang_interpreter returns the name of the interpreter a script starting with
+// a shebang line is run with, without its directory and version suffix, e.g.
+// "#!/usr/bin/env -S python3.11 -u" gives "python".
+pub(crate) fn shebang_interpreter(content: &[u8]) -> Option<&[u8]> {
+    let line = first_line(content).strip_prefix(b"#!")?;
+    let mut tokens = line
+        .split(|b| b.is_ascii_whitespace())
+        .filter(|t| !t.is_empty());
+    let mut cmd = basename(tokens.next()?);
+    if cmd == b"env" {
+        // Skip the options and variable assignments of env to find the command.
+        cmd = loop {
+            let t = tokens.next()?;
+            match t {
+                b"-u" | b"--unset" | b"-C" | b"--chdir" => {
+                    tokens.next();
+                }
+                _ if t.starts_with(b"-S") && t.len() > 2 => break basename(&t[2..]),
+                _ if t.starts_with(b"-") || t.contains(&b'=') => {}
+                _ => break basename(t),
+            }
+        };
     }
-    if content[0] != b'#' || content[1] != b'!' {
-        return false;
+    let end = cmd.len()
+        - cmd
+            .iter()
+            .rev()
+            .take_while(|b| b.is_ascii_digit() || **b == b'.')
+            .count();
+    (end > 0).then_some(&cmd[..end])
+}
+
+fn basename(path: &[u8]) -> &[u8] {
+    match path.iter().rposition(|&b| b == b'/') {
+        Some(i) => &path[i + 1..],
+        None => path,
     }
-    trim_left_ws(trim_right_ws(&content[2..])) == sig
 }
 
 pub(crate) struct SheBangDetector {
-    pub sigs: Vec<&'static [u8]>,
+    pub interpreters: Vec<&'static str>,
 }
 
 impl MimeDetector for SheBangDetector {
     fn detect(&self, content: &[u8], _limit: usize) -> bool {
-        for sig in &self.sigs {
-            if shebang_check(sig, first_line(content)) {
-                return true;
-            }
-        }
-        false
+        let Some(interpreter) = shebang_interpreter(content) else {
+            return false;
+        };
+        self.interpreters
+            .iter()
+            .any(|i| i.as_bytes() == interpreter)
     }
 }
@@ -1,89 +1,21 @@
-use byteorder::{BigEndian, ByteOrder, LittleEndian, ReadBytesExt};
+use byteorder::{BigEndian, ByteOrder};
 
-use crate::{magic::base::OffsetDetector, mime::MimeDetector};
-
-fn class_or_mach_ofat(content: &[u8]) -> bool {
-    if content.len() < 8 {
-        return false;
-    }
-
-    content.starts_with(&[0xCA, 0xFE, 0xBA, 0xBE])
-}
+use crate::{
+    magic::{base::OffsetDetector, macho::is_fat},
+    mime::MimeDetector,
+};
 
 // Class matches a java class file.
 pub(crate) struct ClassDetector;
 
 impl MimeDetector for ClassDetector {
     fn detect(&self, content: &[u8], _: usize) -> bool {
-        class_or_mach_ofat(content) && content[7] > 30
-    }
-}
-
-// MachO matches Mach-O binaries format.
-pub(crate) struct MachODetector;
-
-impl MimeDetector for MachODetector {
-    fn detect(&self, content: &[u8], _: usize) -> bool {
-        if class_or_mach_ofat(content) && content[7] < 20 {
-            return true;
-        }
-
-        if content.len() < 4 {
-            return false;
-        }
-
-        let Ok(be) = std::io::Cursor::new(&content).read_u32::<BigEndian>() else {
-            return false;
-        };
-        let Ok(le) = std::io::Cursor::new(&content).read_u32::<LittleEndian>() else {
-            return false;
-        };
-
-        be == 0xfeedface || le == 0xfeedface || be == 0xfeedfacf || le == 0xfeedfacf
-    }
-}
-
-// ElfObj matches an object file.
-pub(crate) struct ElfObjDetector;
-
-impl MimeDetector for ElfObjDetector {
-    fn detect(&self, content: &[u8], _: usize) -> bool {
-        content.len() > 17
-            && ((content[16] == 0x01 && content[17] == 0x00)
-                || (content[16] == 0x00 && content[17] == 0x01))
-    }
-}
-
-// ElfExe matches an executable file.
-pub(crate) struct ElfExeDetector;
-
-impl MimeDetector for ElfExeDetector {
-    fn detect(&self, content: &[u8], _: usize) -> bool {
-        content.len() > 17
-            && ((content[16] == 0x02 && content[17] == 0x00)
-                || (content[16] == 0x00 && content[17] == 0x02))
-    }
-}
-
-// ElfLib matches a shared library file.
-pub(crate) struct ElfLibDetector;
-
-impl MimeDetector for ElfLibDetector {
-    fn detect(&self, content: &[u8], _: usize) -> bool {
-        content.len() > 17
-            && ((content[16] == 0x03 && content[17] == 0x00)
-                || (content[16] == 0x00 && content[17] == 0x03))
-    }
-}
-
-// ElfDump matches a core dump file.
-pub(crate) struct ElfDumpDetector;
-
-impl MimeDetector for ElfDumpDetector {
-    fn detect(&self, content: &[u8], _: usize) -> bool {
-        content.len() > 17
-            && ((content[16] == 0x04 && content[17] == 0x00)
-                || (content[16] == 0x00 && content[17] == 0x04))
+        // The major version follows the magic and the minor version; 45 is the
+        // version of Java 1.0.
+        content.len() >= 8
+            && content.starts_with(&[0xCA, 0xFE, 0xBA, 0xBE])
+            && BigEndian::read_u16(&content[6..8]) >= 45
+            && !is_fat(content)
     }
 }
 
@@ -0,0 +1,157 @@
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::mime::MimeDetector;
+
+// Ranges of the magic numbers of Python bytecode by Python version. Each
+// version changing the bytecode format takes new magic numbers; Python 3
+// versions allot themselves blocks of them.
+// https://github.com/python/cpython/blob/main/Lib/importlib/_bootstrap_external.py
+const PYC_MAGICS: &[(u16, u16, &str)] = &[
+    (20121, 20121, "1.5"),
+    (50428, 50428, "1.6"),
+    (50823, 50823, "2.0"),
+    (60202, 60202, "2.1"),
+    (60717, 60717, "2.2"),
+    (62011, 62021, "2.3"),
+    (62041, 62061, "2.4"),
+    (62071, 62131, "2.5"),
+    (62151, 62161, "2.6"),
+    (62171, 62211, "2.7"),
+    (3000, 3131, "3.0"),
+    (3141, 3151, "3.1"),
+    (3160, 3180, "3.2"),
+    (3190, 3230, "3.3"),
+    (3250, 3310, "3.4"),
+    (3320, 3351, "3.5"),
+    (3360, 3379, "3.6"),
+    (3390, 3399, "3.7"),
+    (3400, 3419, "3.8"),
+    (3420, 3429, "3.9"),
+    (3430, 3449, "3.10"),
+    (3450, 3499, "3.11"),
+    (3500, 3549, "3.12"),
+    (3550, 3599, "3.13"),
+    (3600, 3649, "3.14"),
+];
+
+// pyc_version returns the Python version of a bytecode file, stored as a
+// little-endian magic number followed by "\r\n".
+fn pyc_version(content: &[u8]) -> Option<&'static str> {
+    if content.len() < 4 || &content[2..4] != b"\r\n" {
+        return None;
+    }
+    let magic = LittleEndian::read_u16(&content[..2]);
+    PYC_MAGICS
+        .iter()
+        .find(|(first, last, _)| (*first..=*last).contains(&magic))
+        .map(|(_, _, v)| *v)
+}
+
+// Pyc matches a compiled Python bytecode file.
+pub(crate) struct PycDetector;
+
+impl MimeDetector for PycDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        pyc_version(content).is_some()
+    }
+}
+
+pub(crate) fn pyc_params(content: &[u8]) -> Vec<(&'static str, String)> {
+    pyc_version(content)
+        .map(|v| vec![("version", v.to_string())])
+        .unwrap_or_default()
+}
+
+// LuaBytecode matches a precompiled Lua chunk. The version byte holds the
+// major and minor versions in its high and low nibbles.
+// https://www.lua.org/source/5.4/lundump.c.html
+pub(crate) struct LuaBytecodeDetector;
+
+impl MimeDetector for LuaBytecodeDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        content.len() > 4 && content.starts_with(b"\x1BLua") && (0x50..=0x5F).contains(&content[4])
+    }
+}
+
+pub(crate) fn lua_bytecode_params(content: &[u8]) -> Vec<(&'static str, String)> {
+    match content.get(4) {
+        Some(v) => vec![("version", format!("{}.{}", v >> 4, v & 0x0F))],
+        None => vec![],
+    }
+}
+
+// three_digits_version returns the version stored as three ASCII digits,
+// followed by a NUL byte, at offset in content.
+fn three_digits_version(content: &[u8], offset: usize) -> Option<&str> {
+    let v = content.get(offset..offset + 4)?;
+    (v[..3].iter().all(u8::is_ascii_digit) && v[3] == 0)
+        .then(|| std::str::from_utf8(&v[..3]).ok())
+        .flatten()
+}
+
+// Dex matches an Android Dalvik executable, "dex\n" followed by the version.
+// ODEX files, optimized for a given device, start with "dey\n" and VDEX files,
+// holding verified DEX files, with "vdex".
+// https://source.android.com/docs/core/runtime/dex-format
+pub(crate) struct DexDetector {
+    pub magic: &'static [u8],
+}
+
+impl MimeDetector for DexDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        content.starts_with(self.magic) && three_digits_version(content, 4).is_some()
+    }
+}
+
+// dex_params reports the format version of DEX, ODEX and VDEX files.
+pub(crate) fn dex_params(content: &[u8]) -> Vec<(&'static str, String)> {
+    three_digits_version(content, 4)
+        .map(|v| vec![("version", v.to_string())])
+        .unwrap_or_default()
+}
+
+// Beam matches a compiled Erlang module, an IFF container of form type BEAM.
+// http://beam-wisdoms.clau.se/en/latest/indepth-beam-file.html
+pub(crate) struct BeamDetector;
+
+impl MimeDetector for BeamDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        content.len() >= 12 && content.starts_with(b"FOR1") && &content[8..12] == b"BEAM"
+    }
+}
+
+// OCaml compiled files start with "Caml1999", a letter telling the kind of
+// file, and the three digits version of the format.
+// https://github.com/ocaml/ocaml/blob/trunk/utils/config.common.ml.in
+pub(crate) struct OcamlDetector {
+    pub kinds: &'static [u8],
+}
+
+impl MimeDetector for OcamlDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        content.len() >= 12
+            && content.starts_with(b"Caml1999")
+            && self.kinds.contains(&content[8])
+            && content[9..12].iter().all(u8::is_ascii_digit)
+    }
+}
+
+pub(crate) fn ocaml_params(content: &[u8]) -> Vec<(&'static str, String)> {
+    match content.get(9..12) {
+        Some(v) if v.iter().all(u8::is_ascii_digit) => {
+            vec![("version", String::from_utf8_lossy(v).into_owned())]
+        }
+        _ => vec![],
+    }
+}
+
+// JavaSerialized matches a Java object serialization stream, the magic
+// followed by the stream version.
+// https://docs.oracle.com/en/java/javase/21/docs/specs/serialization/protocol.html
+pub(crate) struct JavaSerializedDetector;
+
+impl MimeDetector for JavaSerializedDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        content.len() > 4 && content.starts_with(b"\xAC\xED\x00\x05") && content[4] >= 0x70
+    }
+}
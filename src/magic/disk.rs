@@ -0,0 +1,300 @@
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+
+use crate::mime::MimeDetector;
+
+// whole tells whether content is the whole file rather than its first limit
+// bytes, so that trailers can be looked for at its end.
+fn whole(content: &[u8], limit: usize) -> bool {
+    limit == 0 || content.len() < limit
+}
+
+// Trailer matches a file ending with a trailer starting with sig, located
+// offset bytes before the end of the file.
+pub(crate) struct TrailerDetector {
+    pub offset: usize,
+    pub sig: &'static [u8],
+}
+
+impl MimeDetector for TrailerDetector {
+    fn detect(&self, content: &[u8], limit: usize) -> bool {
+        whole(content, limit)
+            && content.len() >= self.offset
+            && content[content.len() - self.offset..].starts_with(self.sig)
+    }
+}
+
+// The volume descriptors of ISO 9660 and UDF start at 32 KiB, each 2 KiB long.
+// Their identifier follows a type byte.
+// https://wiki.osdev.org/ISO_9660
+const VOLUME_DESCRIPTORS: usize = 0x8000;
+
+fn volume_descriptor_ids(content: &[u8]) -> impl Iterator<Item = &[u8]> {
+    // Only the first descriptors are looked at; the set ends with a
+    // terminator anyway.
+    (0..16).map_while(move |i| {
+        let start = VOLUME_DESCRIPTORS + i * 2048 + 1;
+        content.get(start..start + 5)
+    })
+}
+
+// Iso9660 matches an ISO 9660 CD or DVD image, or a UDF image, whose volume
+// recognition sequence starts with BEA01.
+pub(crate) struct Iso9660Detector;
+
+impl MimeDetector for Iso9660Detector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        volume_descriptor_ids(content)
+            .next()
+            .is_some_and(|id| id == b"CD001" || id == b"BEA01")
+    }
+}
+
+// Udf matches a Universal Disk Format image, whose volume recognition sequence
+// holds an NSR descriptor, alone or bridged with ISO 9660.
+// https://www.ecma-international.org/publications-and-standards/standards/ecma-167/
+pub(crate) struct UdfDetector;
+
+impl MimeDetector for UdfDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        volume_descriptor_ids(content).any(|id| id == b"NSR02" || id == b"NSR03")
+    }
+}
+
+// Vhd matches a Microsoft Virtual Hard Disk. The 512 bytes footer ends the
+// file; dynamic disks keep a copy of it at the start. Files created before
+// Virtual PC 2004 have a 511 bytes footer.
+// https://learn.microsoft.com/en-us/windows/win32/vstor/about-vhd
+pub(crate) struct VhdDetector;
+
+impl MimeDetector for VhdDetector {
+    fn detect(&self, content: &[u8], limit: usize) -> bool {
+        content.starts_with(b"conectix")
+            || [512, 511].into_iter().any(|offset| {
+                TrailerDetector {
+                    offset,
+                    sig: b"conectix",
+                }
+                .detect(content, limit)
+            })
+    }
+}
+
+// Vdi matches a VirtualBox Disk Image, a text banner followed by the binary
+// signature at offset 64.
+pub(crate) struct VdiDetector;
+
+impl MimeDetector for VdiDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        content.len() >= 68
+            && content.starts_with(b"<<< ")
+            && LittleEndian::read_u32(&content[64..68]) == 0xBEDA107F
+    }
+}
+
+// Vmdk matches a VMware virtual disk: a hosted sparse extent or an ESX sparse
+// extent, whose magic is followed by a little-endian version, or a text
+// descriptor.
+// https://github.com/libyal/libvmdk/blob/main/documentation/VMWare%20Virtual%20Disk%20Format%20(VMDK).asciidoc
+pub(crate) struct VmdkDetector;
+
+impl MimeDetector for VmdkDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        let version = content.get(4..8).map(LittleEndian::read_u32);
+        (content.starts_with(b"KDMV") && version.is_some_and(|v| (1..=3).contains(&v)))
+            || (content.starts_with(b"COWD") && version == Some(1))
+            || content.starts_with(b"# Disk DescriptorFile")
+    }
+}
+
+// qcow_params reports the version of a QEMU copy-on-write image.
+pub(crate) fn qcow_params(content: &[u8]) -> Vec<(&'static str, String)> {
+    match content.get(4..8) {
+        Some(v) => vec![("version", BigEndian::read_u32(v).to_string())],
+        None => vec![],
+    }
+}
+
+// squashfs_version returns the version of a SquashFS filesystem, stored at 28
+// in the byte order of the magic.
+fn squashfs_version(content: &[u8]) -> Option<(u16, u16)> {
+    let v = content.get(28..32)?;
+    let read: fn(&[u8]) -> u16 = match &content[..4] {
+        b"hsqs" => LittleEndian::read_u16,
+        b"sqsh" => BigEndian::read_u16,
+        _ => return None,
+    };
+    Some((read(&v[..2]), read(&v[2..])))
+}
+
+// SquashFs matches a SquashFS filesystem image. The magic is plain text, so
+// the major version must be a known one as well.
+pub(crate) struct SquashFsDetector;
+
+impl MimeDetector for SquashFsDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        squashfs_version(content).is_some_and(|(major, _)| (1..=4).contains(&major))
+    }
+}
+
+pub(crate) fn squashfs_params(content: &[u8]) -> Vec<(&'static str, String)> {
+    squashfs_version(content)
+        .map(|(major, minor)| vec![("version", format!("{}.{}", major, minor))])
+        .unwrap_or_default()
+}
+
+// The ext2 superblock starts at 1024, its magic number at 56 within.
+// https://www.kernel.org/doc/html/latest/filesystems/ext4/super.html
+const EXT_SUPERBLOCK: usize = 1024;
+
+// ext_version tells ext2, ext3 and ext4 filesystems apart from the features
+// they use: ext3 adds a journal, ext4 features such as extents.
+fn ext_version(content: &[u8]) -> Option<&'static str> {
+    let sb = content.get(EXT_SUPERBLOCK..EXT_SUPERBLOCK + 104)?;
+    if LittleEndian::read_u16(&sb[56..58]) != 0xEF53 {
+        return None;
+    }
+    // Only revisions 0 and 1 exist, and blocks are 1 KiB to 64 KiB.
+    let log_block_size = LittleEndian::read_u32(&sb[24..28]);
+    let rev_level = LittleEndian::read_u32(&sb[76..80]);
+    if rev_level > 1 || log_block_size > 6 {
+        return None;
+    }
+    let compat = LittleEndian::read_u32(&sb[92..96]);
+    let incompat = LittleEndian::read_u32(&sb[96..100]);
+    let ro_compat = LittleEndian::read_u32(&sb[100..104]);
+    // Extents, 64 bits, flex_bg and inline data; huge files, group descriptor
+    // checksums, directory nlink, extra inode size and metadata checksums.
+    if incompat & 0x82C0 != 0 || ro_compat & 0x0478 != 0 {
+        Some("ext4")
+    } else if compat & 0x0004 != 0 {
+        Some("ext3")
+    } else {
+        Some("ext2")
+    }
+}
+
+// Ext matches an ext2, ext3 or ext4 filesystem image.
+pub(crate) struct ExtDetector;
+
+impl MimeDetector for ExtDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        ext_version(content).is_some()
+    }
+}
+
+// boot_sector tells whether content starts with a boot sector: a jump over
+// the BIOS parameter block and the 0x55AA signature at the end of the sector.
+fn boot_sector(content: &[u8]) -> bool {
+    content.len() >= 512
+        && (content[0] == 0xEB || content[0] == 0xE9)
+        && content[510..512] == [0x55, 0xAA]
+}
+
+// fat_type returns the FAT variant named in the boot sector. FAT32 moves the
+// name further, after its extended BIOS parameter block.
+// https://learn.microsoft.com/en-us/windows/win32/fileio/exfat-specification
+fn fat_type(content: &[u8]) -> Option<&'static str> {
+    if !boot_sector(content) {
+        return None;
+    }
+    match (&content[54..62], &content[82..90]) {
+        (b"FAT12   ", _) => Some("fat12"),
+        (b"FAT16   ", _) => Some("fat16"),
+        (b"FAT     ", _) => Some("fat"),
+        (_, b"FAT32   ") => Some("fat32"),
+        _ => None,
+    }
+}
+
+// Fat matches a FAT12, FAT16 or FAT32 filesystem image.
+pub(crate) struct FatDetector;
+
+impl MimeDetector for FatDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        fat_type(content).is_some()
+    }
+}
+
+// BootSector matches a filesystem image whose boot sector names the
+// filesystem, e.g. "NTFS    ", after the jump instruction.
+pub(crate) struct BootSectorDetector {
+    pub oem: &'static [u8],
+}
+
+impl MimeDetector for BootSectorDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        boot_sector(content) && content[3..].starts_with(self.oem)
+    }
+}
+
+// Gpt matches a disk image with a GUID partition table, whose header follows
+// the protective MBR, in the second 512 bytes or 4 KiB sector.
+pub(crate) struct GptDetector;
+
+impl MimeDetector for GptDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        [512, 4096]
+            .into_iter()
+            .any(|offset| content.get(offset..offset + 8) == Some(b"EFI PART"))
+    }
+}
+
+// Mbr matches a disk image with a master boot record partition table. The
+// boot signature alone is common in other boot sectors, so the four partition
+// entries must be valid as well: one of them used, and the used ones starting
+// past the MBR without overlapping.
+pub(crate) struct MbrDetector;
+
+impl MimeDetector for MbrDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        if content.len() < 512 || content[510..512] != [0x55, 0xAA] {
+            return false;
+        }
+        let entries: Vec<&[u8]> = content[446..510].chunks_exact(16).collect();
+        if !entries.iter().all(|e| e[0] == 0x00 || e[0] == 0x80) {
+            return false;
+        }
+        // The start and size, in sectors, of the used entries. Sector 0 holds
+        // the MBR itself, so no partition starts there.
+        let mut used: Vec<(u64, u64)> = entries
+            .iter()
+            .filter(|e| e[4] != 0)
+            .map(|e| {
+                let start = LittleEndian::read_u32(&e[8..12]) as u64;
+                (start, LittleEndian::read_u32(&e[12..16]) as u64)
+            })
+            .collect();
+        if used.is_empty() || used.iter().any(|&(start, size)| start == 0 || size == 0) {
+            return false;
+        }
+        used.sort_unstable();
+        used.windows(2).all(|w| w[0].0 + w[0].1 <= w[1].0)
+    }
+}
+
+// raw_image_params reports what a raw disk image holds: its filesystem, its
+// partition table or its encryption.
+pub(crate) fn raw_image_params(content: &[u8]) -> Vec<(&'static str, String)> {
+    if content.starts_with(b"LUKS\xBA\xBE") && content.len() >= 8 {
+        let version = BigEndian::read_u16(&content[6..8]);
+        return vec![
+            ("encryption", "luks".to_string()),
+            ("version", version.to_string()),
+        ];
+    }
+    let filesystem = ext_version(content).or_else(|| match content.get(3..11) {
+        Some(b"NTFS    ") if boot_sector(content) => Some("ntfs"),
+        Some(b"EXFAT   ") if boot_sector(content) => Some("exfat"),
+        _ => fat_type(content),
+    });
+    if let Some(fs) = filesystem {
+        return vec![("filesystem", fs.to_string())];
+    }
+    if GptDetector.detect(content, 0) {
+        vec![("partitions", "gpt".to_string())]
+    } else if MbrDetector.detect(content, 0) {
+        vec![("partitions", "mbr".to_string())]
+    } else {
+        vec![]
+    }
+}
@@ -0,0 +1,173 @@
+use crate::mime::MimeDetector;
+
+// Element IDs of the EBML header and of the Matroska elements leading to the
+// track types.
+// https://www.rfc-editor.org/rfc/rfc8794.html
+// https://www.rfc-editor.org/rfc/rfc9559.html
+const EBML: u32 = 0x1A45DFA3;
+const DOC_TYPE: u32 = 0x4282;
+const DOC_TYPE_VERSION: u32 = 0x4287;
+const SEGMENT: u32 = 0x18538067;
+const TRACKS: u32 = 0x1654AE6B;
+const CLUSTER: u32 = 0x1F43B675;
+const TRACK_ENTRY: u32 = 0xAE;
+const TRACK_TYPE: u32 = 0x83;
+const VIDEO: u32 = 0xE0;
+const STEREO_MODE: u32 = 0x53B8;
+
+// Element is an EBML element: its ID, including the length marker, and its
+// data. The data is cut short when the content is truncated.
+struct Element<'a> {
+    id: u32,
+    data: &'a [u8],
+    truncated: bool,
+}
+
+// Elements iterates over the EBML elements of data.
+struct Elements<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Iterator for Elements<'a> {
+    type Item = Element<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let data = self.data;
+        // IDs are at most 4 bytes long and keep their length marker.
+        let (id, id_len) = vint(data, 4)?;
+        let id = (id | 1 << (7 * id_len)) as u32;
+        let Some((size, size_len)) = vint(&data[id_len..], 8) else {
+            self.data = &[];
+            return None;
+        };
+        let start = id_len + size_len;
+        // All the value bits set means an unknown size, the element extends to
+        // the end of its parent.
+        let end = if size == (1 << (7 * size_len)) - 1 {
+            data.len()
+        } else {
+            start.saturating_add(size as usize)
+        };
+        let truncated = end > data.len();
+        let end = end.min(data.len());
+        self.data = &data[end..];
+        Some(Element {
+            id,
+            data: &data[start..end],
+            truncated,
+        })
+    }
+}
+
+fn elements(data: &[u8]) -> Elements<'_> {
+    Elements { data }
+}
+
+// vint reads a variable size integer of at most max bytes, returning its value
+// without the length marker and its length.
+fn vint(data: &[u8], max: usize) -> Option<(u64, usize)> {
+    let first = *data.first()?;
+    let len = first.leading_zeros() as usize + 1;
+    if len > max || data.len() < len {
+        return None;
+    }
+    let value = data[1..len]
+        .iter()
+        .fold(first as u64 & (0xFF >> len), |v, &b| v << 8 | b as u64);
+    Some((value, len))
+}
+
+// uint reads the value of an unsigned integer element.
+fn uint(data: &[u8]) -> Option<u64> {
+    (data.len() <= 8).then(|| data.iter().fold(0, |v, &b| v << 8 | b as u64))
+}
+
+// Header holds the document type of an EBML file, e.g. "matroska" or "webm",
+// and the version of the document type specification the file uses.
+struct Header<'a> {
+    doc_type: &'a [u8],
+    doc_type_version: u64,
+}
+
+// header parses the EBML header which opens content.
+fn header(content: &[u8]) -> Option<Header<'_>> {
+    let ebml = elements(content).next().filter(|e| e.id == EBML)?;
+    let mut h = Header {
+        doc_type: &[],
+        doc_type_version: 1,
+    };
+    for e in elements(ebml.data) {
+        match e.id {
+            DOC_TYPE => h.doc_type = e.data.strip_suffix(b"\0").unwrap_or(e.data),
+            DOC_TYPE_VERSION => h.doc_type_version = uint(e.data)?,
+            _ => {}
+        }
+    }
+    (!h.doc_type.is_empty() && h.doc_type_version > 0).then_some(h)
+}
+
+// Tracks counts the tracks of a Matroska segment by type.
+#[derive(Default)]
+struct Tracks {
+    video: usize,
+    audio: usize,
+    stereo: bool,
+}
+
+// tracks reads the Tracks element of the segment following the EBML header.
+// Returns None when the element is not entirely within content.
+fn tracks(content: &[u8]) -> Option<Tracks> {
+    let mut top = elements(content);
+    top.next().filter(|e| e.id == EBML)?;
+    let segment = top.next().filter(|e| e.id == SEGMENT)?;
+    let tracks = elements(segment.data)
+        .take_while(|e| e.id != CLUSTER)
+        .find(|e| e.id == TRACKS)
+        .filter(|e| !e.truncated)?;
+
+    let mut t = Tracks::default();
+    for entry in elements(tracks.data).filter(|e| e.id == TRACK_ENTRY) {
+        for e in elements(entry.data) {
+            match e.id {
+                TRACK_TYPE if uint(e.data) == Some(1) => t.video += 1,
+                TRACK_TYPE if uint(e.data) == Some(2) => t.audio += 1,
+                // Mono, the default stereo mode, is 0.
+                VIDEO => {
+                    t.stereo |= elements(e.data)
+                        .any(|s| s.id == STEREO_MODE && uint(s.data).is_some_and(|m| m != 0))
+                }
+                _ => {}
+            }
+        }
+    }
+    Some(t)
+}
+
+// EbmlDocType matches an EBML file of the given document type.
+pub(crate) struct EbmlDocTypeDetector {
+    pub doc_type: &'static [u8],
+}
+
+impl MimeDetector for EbmlDocTypeDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        header(content).is_some_and(|h| h.doc_type == self.doc_type)
+    }
+}
+
+// MatroskaAudio matches a Matroska or WebM file holding only audio tracks.
+pub(crate) struct MatroskaAudioDetector;
+
+impl MimeDetector for MatroskaAudioDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        tracks(content).is_some_and(|t| t.audio > 0 && t.video == 0)
+    }
+}
+
+// Matroska3d matches a Matroska file with a stereoscopic video track.
+pub(crate) struct Matroska3dDetector;
+
+impl MimeDetector for Matroska3dDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        tracks(content).is_some_and(|t| t.stereo)
+    }
+}
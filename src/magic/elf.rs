@@ -0,0 +1,285 @@
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+
+use crate::mime::MimeDetector;
+
+use super::base::BytesExt;
+
+const ET_REL: u16 = 1;
+const ET_EXEC: u16 = 2;
+const ET_DYN: u16 = 3;
+const ET_CORE: u16 = 4;
+const PT_DYNAMIC: u32 = 2;
+const PT_INTERP: u32 = 3;
+const DT_NULL: u64 = 0;
+const DT_FLAGS_1: u64 = 0x6FFFFFFB;
+const DF_1_PIE: u64 = 0x08000000;
+
+// Elf reads the header of an Executable and Linkable Format file, whose fields
+// are 32 or 64 bits wide and little or big endian as told by the identification
+// bytes.
+// https://refspecs.linuxfoundation.org/elf/gabi4+/ch4.eheader.html
+struct Elf<'a> {
+    content: &'a [u8],
+    is_64: bool,
+    big_endian: bool,
+}
+
+impl<'a> Elf<'a> {
+    fn parse(content: &'a [u8]) -> Option<Self> {
+        if !content.starts_with(b"\x7FELF") || content.len() < 52 {
+            return None;
+        }
+        let is_64 = match content[4] {
+            1 => false,
+            2 => true,
+            _ => return None,
+        };
+        let big_endian = match content[5] {
+            1 => false,
+            2 => true,
+            _ => return None,
+        };
+        Some(Elf {
+            content,
+            is_64,
+            big_endian,
+        })
+    }
+
+    fn u16(&self, offset: usize) -> Option<u16> {
+        let b = self.content.get(offset..offset.checked_add(2)?)?;
+        Some(if self.big_endian {
+            BigEndian::read_u16(b)
+        } else {
+            LittleEndian::read_u16(b)
+        })
+    }
+
+    fn u32(&self, offset: usize) -> Option<u32> {
+        let b = self.content.get(offset..offset.checked_add(4)?)?;
+        Some(if self.big_endian {
+            BigEndian::read_u32(b)
+        } else {
+            LittleEndian::read_u32(b)
+        })
+    }
+
+    // word reads an address, offset or size field, 32 or 64 bits wide.
+    fn word(&self, offset: usize) -> Option<u64> {
+        if !self.is_64 {
+            return self.u32(offset).map(u64::from);
+        }
+        let b = self.content.get(offset..offset.checked_add(8)?)?;
+        Some(if self.big_endian {
+            BigEndian::read_u64(b)
+        } else {
+            LittleEndian::read_u64(b)
+        })
+    }
+
+    fn file_type(&self) -> Option<u16> {
+        self.u16(16)
+    }
+
+    fn machine(&self) -> Option<u16> {
+        self.u16(18)
+    }
+
+    // program_headers returns the type, file offset and file size of the
+    // program headers within content.
+    fn program_headers(&self) -> Vec<(u32, u64, u64)> {
+        let (phoff, entsize, num) = if self.is_64 {
+            (self.word(32), self.u16(54), self.u16(56))
+        } else {
+            (self.word(28), self.u16(42), self.u16(44))
+        };
+        let (Some(phoff), Some(entsize), Some(num)) = (phoff, entsize, num) else {
+            return vec![];
+        };
+        // Offsets of p_offset and p_filesz in a program header.
+        let (off, size) = if self.is_64 { (8, 32) } else { (4, 16) };
+        (0..num as u64)
+            .map_while(|i| {
+                let ph = usize::try_from(phoff.checked_add(i * entsize as u64)?).ok()?;
+                Some((
+                    self.u32(ph)?,
+                    self.word(ph.saturating_add(off))?,
+                    self.word(ph.saturating_add(size))?,
+                ))
+            })
+            .collect()
+    }
+
+    // is_pie reports whether a shared object is a position independent
+    // executable. The DF_1_PIE flag of the dynamic section tells so, if the
+    // section is within content and has flags; older linkers did not set it.
+    // Otherwise, having a program interpreter does.
+    fn is_pie(&self) -> bool {
+        let headers = self.program_headers();
+        let dynamic = headers.iter().find(|(t, _, _)| *t == PT_DYNAMIC);
+        if let Some(flags) = dynamic.and_then(|&(_, off, size)| self.flags_1(off, size)) {
+            return flags & DF_1_PIE != 0;
+        }
+        headers.iter().any(|(t, _, _)| *t == PT_INTERP)
+    }
+
+    // flags_1 reads the DT_FLAGS_1 entry of the dynamic section.
+    fn flags_1(&self, offset: u64, size: u64) -> Option<u64> {
+        let step = if self.is_64 { 16 } else { 8 };
+        let offset = usize::try_from(offset).ok()?;
+        let end = offset.checked_add(usize::try_from(size).ok()?)?;
+        for entry in (offset..end).step_by(step) {
+            match self.word(entry)? {
+                DT_NULL => return None,
+                DT_FLAGS_1 => return self.word(entry + step / 2),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    // has_section reports whether the file has a section called name, read
+    // from the section header string table, when it is within content.
+    fn has_section(&self, name: &[u8]) -> bool {
+        let (shoff, entsize, num, strndx) = if self.is_64 {
+            (self.word(40), self.u16(58), self.u16(60), self.u16(62))
+        } else {
+            (self.word(32), self.u16(46), self.u16(48), self.u16(50))
+        };
+        let (Some(shoff), Some(entsize), Some(num), Some(strndx)) = (shoff, entsize, num, strndx)
+        else {
+            return false;
+        };
+        // Offsets of sh_offset and sh_size in a section header.
+        let (off, size) = if self.is_64 { (24, 32) } else { (16, 20) };
+        let header = |i: u16| usize::try_from(shoff.checked_add(i as u64 * entsize as u64)?).ok();
+        let strtab = header(strndx).and_then(|h| {
+            let start = usize::try_from(self.word(h.saturating_add(off))?).ok()?;
+            let end =
+                start.checked_add(usize::try_from(self.word(h.saturating_add(size))?).ok()?)?;
+            self.content.get(start..end)
+        });
+        let Some(strtab) = strtab else {
+            return false;
+        };
+        (0..num).any(|i| {
+            header(i)
+                .and_then(|h| self.u32(h))
+                .and_then(|n| strtab.get(n as usize..))
+                .is_some_and(|s| s.starts_with(name) && s.get(name.len()) == Some(&0))
+        })
+    }
+}
+
+// elf_params reports the class, endianness, machine and OS ABI of an ELF file.
+pub(crate) fn elf_params(content: &[u8]) -> Vec<(&'static str, String)> {
+    let Some(elf) = Elf::parse(content) else {
+        return vec![];
+    };
+    let mut params = vec![
+        ("class", if elf.is_64 { "64" } else { "32" }.to_string()),
+        (
+            "endianness",
+            if elf.big_endian { "big" } else { "little" }.to_string(),
+        ),
+    ];
+    let machine = match elf.machine() {
+        Some(2) => Some("sparc"),
+        Some(3) => Some("x86"),
+        Some(8) => Some("mips"),
+        Some(20) => Some("ppc"),
+        Some(21) => Some("ppc64"),
+        Some(22) => Some("s390"),
+        Some(40) => Some("arm"),
+        Some(43) => Some("sparcv9"),
+        Some(62) => Some("x86_64"),
+        Some(183) => Some("aarch64"),
+        Some(243) => Some("riscv"),
+        Some(247) => Some("bpf"),
+        Some(258) => Some("loongarch"),
+        _ => None,
+    };
+    if let Some(m) = machine {
+        params.push(("machine", m.to_string()));
+    }
+    let abi = match content[7] {
+        0 => Some("sysv"),
+        1 => Some("hpux"),
+        2 => Some("netbsd"),
+        3 => Some("linux"),
+        6 => Some("solaris"),
+        9 => Some("freebsd"),
+        12 => Some("openbsd"),
+        97 => Some("arm"),
+        255 => Some("standalone"),
+        _ => None,
+    };
+    if let Some(a) = abi {
+        params.push(("abi", a.to_string()));
+    }
+    params
+}
+
+// ElfObj matches an object file.
+pub(crate) struct ElfObjDetector;
+
+impl MimeDetector for ElfObjDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        Elf::parse(content).is_some_and(|e| e.file_type() == Some(ET_REL))
+    }
+}
+
+// ElfExe matches an executable file, including position independent ones.
+pub(crate) struct ElfExeDetector;
+
+impl MimeDetector for ElfExeDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        Elf::parse(content).is_some_and(|e| match e.file_type() {
+            Some(ET_EXEC) => true,
+            Some(ET_DYN) => e.is_pie(),
+            _ => false,
+        })
+    }
+}
+
+// ElfLib matches a shared library file.
+pub(crate) struct ElfLibDetector;
+
+impl MimeDetector for ElfLibDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        Elf::parse(content).is_some_and(|e| e.file_type() == Some(ET_DYN))
+    }
+}
+
+// ElfDump matches a core dump file.
+pub(crate) struct ElfDumpDetector;
+
+impl MimeDetector for ElfDumpDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        Elf::parse(content).is_some_and(|e| e.file_type() == Some(ET_CORE))
+    }
+}
+
+// KernelModule matches a Linux kernel module, an object file with a .modinfo
+// section. When the section headers, at the end of the file, are out of reach,
+// the vermagic entry of the section is looked for instead.
+pub(crate) struct KernelModuleDetector;
+
+impl MimeDetector for KernelModuleDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        Elf::parse(content)
+            .is_some_and(|e| e.has_section(b".modinfo") || content.index(b"\0vermagic=").is_some())
+    }
+}
+
+// AppImage matches an AppImage, an ELF runtime followed by a filesystem image
+// holding the application. The magic bytes use the padding of the ELF
+// identification.
+// https://github.com/AppImage/AppImageSpec/blob/master/draft.md
+pub(crate) struct AppImageDetector;
+
+impl MimeDetector for AppImageDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        content.len() > 11 && matches!(&content[8..11], b"AI\x01" | b"AI\x02")
+    }
+}
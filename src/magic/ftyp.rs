@@ -1,3 +1,5 @@
+use byteorder::{BigEndian, ByteOrder};
+
 use crate::mime::MimeDetector;
 
 // QuickTime matches a QuickTime File Format file.
@@ -13,10 +15,10 @@ impl MimeDetector for QuickTimeDetector {
         }
         // First 4 bytes represent the size of the atom as unsigned int.
         // Next 4 bytes are the type of the atom.
-        // For `ftyp` atoms check if first byte in size is 0, otherwise, a text file
-        // which happens to contain 'ftypqt  ' at index 4 will trigger a false positive.
-        if &content[4..12] == b"ftypqt  " || &content[4..8] == b"ftypmoov" {
-            return content[0] == 0x00;
+        // Files with an `ftyp` atom are QuickTime movies only when it is their
+        // major brand; MP4 files may list `qt  ` among their compatible brands.
+        if &content[4..8] == b"ftyp" {
+            return Ftyp::parse(content).is_some_and(|f| f.major == b"qt  " || f.major == b"moov");
         }
         let basic_atom_types = vec![
             b"moov\x00",
@@ -33,3 +35,138 @@ impl MimeDetector for QuickTimeDetector {
         &content[..8] == b"\x00\x00\x00\x08wide"
     }
 }
+
+// Boxes iterates over the ISO base media file format boxes of data, yielding
+// their type and content. The content is cut short when data is truncated.
+// https://developer.apple.com/documentation/quicktime-file-format/atoms
+pub(crate) struct Boxes<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Boxes<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Boxes { data }
+    }
+}
+
+impl<'a> Iterator for Boxes<'a> {
+    type Item = (&'a [u8], &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let data = self.data;
+        if data.len() < 8 {
+            return None;
+        }
+        let (typ, mut header) = (&data[4..8], 8);
+        let size = match BigEndian::read_u32(&data[..4]) as u64 {
+            // The box extends to the end of the data.
+            0 => data.len() as u64,
+            // The size is a 64 bits integer following the type.
+            1 if data.len() >= 16 => {
+                header = 16;
+                BigEndian::read_u64(&data[8..16])
+            }
+            size => size,
+        };
+        if size < header as u64 {
+            self.data = &[];
+            return None;
+        }
+        let end = size.min(data.len() as u64) as usize;
+        self.data = &data[end..];
+        Some((typ, &data[header..end]))
+    }
+}
+
+// Ftyp is the file type box opening an ISO base media file: the major brand,
+// the specification the file is best used with, and the compatible brands.
+pub(crate) struct Ftyp<'a> {
+    pub major: &'a [u8],
+    pub compatible: Vec<&'a [u8]>,
+}
+
+impl<'a> Ftyp<'a> {
+    // parse reads the ftyp box at the start of content. The box holds the major
+    // brand, a minor version and a list of compatible brands.
+    pub(crate) fn parse(content: &'a [u8]) -> Option<Self> {
+        let (typ, data) = Boxes::new(content).next()?;
+        // The size is checked, otherwise a text file which happens to contain
+        // "ftyp" at index 4 would be parsed.
+        let size = BigEndian::read_u32(&content[..4]) as usize;
+        if typ != b"ftyp" || !(16..=1024).contains(&size) || size % 4 != 0 || data.len() < 8 {
+            return None;
+        }
+        Some(Ftyp {
+            major: &data[..4],
+            compatible: data[8..].chunks_exact(4).collect(),
+        })
+    }
+
+    // has checks whether brand is the major brand or a compatible brand.
+    pub(crate) fn has(&self, brand: &[u8]) -> bool {
+        self.major == brand || self.compatible.contains(&brand)
+    }
+}
+
+// Brands which do not tell what a file holds on their own; the compatible
+// brands of files with such a major brand are looked at as well.
+const GENERIC_BRANDS: &[&[u8]] = &[
+    b"isom", b"iso2", b"iso3", b"iso4", b"iso5", b"iso6", b"iso7", b"iso8", b"iso9", b"mp41",
+    b"mp42", b"mif1", b"msf1", b"miaf",
+];
+
+// FtypBrands matches an ISO base media file whose major brand is one of brands,
+// or whose major brand is generic and one of its compatible brands is one of brands.
+pub(crate) struct FtypBrandsDetector {
+    pub brands: Vec<&'static [u8]>,
+}
+
+impl MimeDetector for FtypBrandsDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        let Some(ftyp) = Ftyp::parse(content) else {
+            return false;
+        };
+        self.brands.contains(&ftyp.major)
+            || (GENERIC_BRANDS.contains(&ftyp.major)
+                && ftyp.compatible.iter().any(|b| self.brands.contains(b)))
+    }
+}
+
+// AvifSequence matches an AVIF image sequence, which lists the "avis" brand.
+pub(crate) struct AvifSequenceDetector;
+
+impl MimeDetector for AvifSequenceDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        Ftyp::parse(content).is_some_and(|f| f.has(b"avis"))
+    }
+}
+
+// Mp4Audio matches an MP4 file whose tracks, as listed in the movie box, are
+// all audio tracks. The movie box is often at the end of the file, out of reach.
+pub(crate) struct Mp4AudioDetector;
+
+impl MimeDetector for Mp4AudioDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        let Some((_, moov)) = Boxes::new(content).find(|(t, _)| *t == b"moov") else {
+            return false;
+        };
+        let (mut audio, mut other) = (0, 0);
+        for (_, trak) in Boxes::new(moov).filter(|(t, _)| *t == b"trak") {
+            match track_handler(trak) {
+                Some(b"soun") => audio += 1,
+                Some(_) => other += 1,
+                None => return false,
+            }
+        }
+        audio > 0 && other == 0
+    }
+}
+
+// track_handler returns the handler type of a track, e.g. "vide" or "soun",
+// from its trak/mdia/hdlr box.
+fn track_handler(trak: &[u8]) -> Option<&[u8]> {
+    let (_, mdia) = Boxes::new(trak).find(|(t, _)| *t == b"mdia")?;
+    let (_, hdlr) = Boxes::new(mdia).find(|(t, _)| *t == b"hdlr")?;
+    // Version, flags and pre-defined fields precede the handler type.
+    hdlr.get(8..12)
+}
@@ -1,4 +1,8 @@
-use crate::mime::MimeDetector;
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+
+use crate::mime::{Mime, MimeDetector};
+
+use super::ftyp::Boxes;
 
 pub(crate) struct Jpeg2kDetector {
     pub sig: &'static [u8],
@@ -16,17 +20,6 @@ impl MimeDetector for Jpeg2kDetector {
     }
 }
 
-// Webp matches a WebP file.
-pub(crate) struct WebpDetector;
-
-impl MimeDetector for WebpDetector {
-    fn detect(&self, content: &[u8], _: usize) -> bool {
-        content.len() > 12
-            && &content[0..4] == b"RIFF"
-            && content[8..12] == [0x57, 0x45, 0x42, 0x50]
-    }
-}
-
 // Dwg matches a CAD drawing file.
 pub(crate) struct DwgDetector;
 
@@ -71,3 +64,374 @@ impl MimeDetector for JxlDetector {
             || content.starts_with(b"\x00\x00\x00\x0cJXL\x20\x0d\x0a\x87\x0a")
     }
 }
+
+/// ColorType is the colour model of the pixels of an image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorType {
+    Grayscale,
+    GrayscaleAlpha,
+    Rgb,
+    Rgba,
+    /// Pixels are indexes into a palette.
+    Indexed,
+    Cmyk,
+}
+
+/// ImageInfo holds the properties of an image read from its header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageInfo {
+    /// The width of the image, in pixels.
+    pub width: u32,
+    /// The height of the image, in pixels.
+    pub height: u32,
+    /// The number of bits per channel, or per palette index, if known.
+    pub bit_depth: Option<u8>,
+    /// The colour model of the pixels, if known.
+    pub color_type: Option<ColorType>,
+    /// Whether the image has more than one frame.
+    pub animated: bool,
+    /// The EXIF orientation, from 1 (upright) to 8, if recorded in the header.
+    pub orientation: Option<u8>,
+}
+
+impl ImageInfo {
+    fn new(width: u32, height: u32) -> Self {
+        ImageInfo {
+            width,
+            height,
+            bit_depth: None,
+            color_type: None,
+            animated: false,
+            orientation: None,
+        }
+    }
+}
+
+// read_image_info reads the properties of an image detected as mime.
+pub(crate) fn read_image_info(mime: &Mime, content: &[u8]) -> Option<ImageInfo> {
+    match mime.mime.as_str() {
+        "image/png" | "image/vnd.mozilla.apng" => png_info(content),
+        "image/jpeg" => jpeg_info(content),
+        "image/gif" => gif_info(content),
+        "image/webp" => webp_info(content),
+        "image/bmp" => bmp_info(content),
+        "image/avif" | "image/heic" | "image/heif" => heif_info(content, false),
+        "image/avif-sequence" | "image/heic-sequence" | "image/heif-sequence" => {
+            heif_info(content, true)
+        }
+        _ => None,
+    }
+}
+
+// https://www.w3.org/TR/png/#5Chunk-layout
+fn png_info(content: &[u8]) -> Option<ImageInfo> {
+    let ihdr = content.get(8..33).filter(|h| &h[4..8] == b"IHDR")?;
+    let mut info = ImageInfo::new(
+        BigEndian::read_u32(&ihdr[8..12]),
+        BigEndian::read_u32(&ihdr[12..16]),
+    );
+    info.bit_depth = Some(ihdr[16]);
+    info.color_type = match ihdr[17] {
+        0 => Some(ColorType::Grayscale),
+        2 => Some(ColorType::Rgb),
+        3 => Some(ColorType::Indexed),
+        4 => Some(ColorType::GrayscaleAlpha),
+        6 => Some(ColorType::Rgba),
+        _ => None,
+    };
+
+    let mut chunks = &content[8..];
+    while chunks.len() >= 8 {
+        let len = BigEndian::read_u32(&chunks[..4]) as usize;
+        let data = &chunks[8..(8 + len).min(chunks.len())];
+        match &chunks[4..8] {
+            b"acTL" => info.animated = data.len() >= 4 && BigEndian::read_u32(&data[..4]) > 1,
+            b"eXIf" => info.orientation = exif_orientation(data),
+            b"IDAT" | b"IEND" => break,
+            _ => {}
+        }
+        // Length, type, data and CRC.
+        chunks = chunks.get(12 + len..).unwrap_or(&[]);
+    }
+    Some(info)
+}
+
+// https://www.w3.org/Graphics/JPEG/itu-t81.pdf, annex B.
+fn jpeg_info(content: &[u8]) -> Option<ImageInfo> {
+    let mut orientation = None;
+    let mut i = 2;
+    while i + 4 <= content.len() {
+        if content[i] != 0xFF {
+            return None;
+        }
+        let marker = content[i + 1];
+        // Fill bytes and markers without a segment.
+        if marker == 0xFF || marker == 0x01 || (0xD0..=0xD8).contains(&marker) {
+            i += if marker == 0xFF { 1 } else { 2 };
+            continue;
+        }
+        // The length counts itself.
+        let len = BigEndian::read_u16(&content[i + 2..i + 4]) as usize;
+        if len < 2 {
+            return None;
+        }
+        let end = (i + 2 + len).min(content.len());
+        let data = &content[(i + 4).min(end)..end];
+        match marker {
+            // APP1 holding EXIF metadata.
+            0xE1 if data.starts_with(b"Exif\0\0") => orientation = exif_orientation(&data[6..]),
+            // Start of frame, except DHT, JPG and DAC which share the range.
+            0xC0..=0xCF if marker != 0xC4 && marker != 0xC8 && marker != 0xCC => {
+                let frame = data.get(..6)?;
+                let mut info = ImageInfo::new(
+                    BigEndian::read_u16(&frame[3..5]) as u32,
+                    BigEndian::read_u16(&frame[1..3]) as u32,
+                );
+                info.bit_depth = Some(frame[0]);
+                info.color_type = match frame[5] {
+                    1 => Some(ColorType::Grayscale),
+                    3 => Some(ColorType::Rgb),
+                    4 => Some(ColorType::Cmyk),
+                    _ => None,
+                };
+                info.orientation = orientation;
+                return Some(info);
+            }
+            // Start of scan: the frame header was missing.
+            0xDA => return None,
+            _ => {}
+        }
+        i += 2 + len;
+    }
+    None
+}
+
+// https://www.w3.org/Graphics/GIF/spec-gif89a.txt
+fn gif_info(content: &[u8]) -> Option<ImageInfo> {
+    let header = content.get(..13)?;
+    let mut info = ImageInfo::new(
+        LittleEndian::read_u16(&header[6..8]) as u32,
+        LittleEndian::read_u16(&header[8..10]) as u32,
+    );
+    let packed = header[10];
+    info.color_type = Some(ColorType::Indexed);
+    // The size of the global color table, or else the color resolution.
+    info.bit_depth = Some(match packed & 0x80 {
+        0 => ((packed >> 4) & 0x07) + 1,
+        _ => (packed & 0x07) + 1,
+    });
+
+    let mut i = 13;
+    if packed & 0x80 != 0 {
+        i += 3 << ((packed & 0x07) + 1);
+    }
+    let mut frames = 0;
+    while let Some(&block) = content.get(i) {
+        match block {
+            // Extension: label and data sub-blocks.
+            0x21 => {
+                if content
+                    .get(i + 2..)
+                    .is_some_and(|e| e.starts_with(b"\x0bNETSCAPE2.0"))
+                {
+                    info.animated = true;
+                    break;
+                }
+                i = gif_skip_sub_blocks(content, i + 2);
+            }
+            // Image descriptor, optional local color table, LZW code size and data sub-blocks.
+            0x2C => {
+                frames += 1;
+                if frames > 1 {
+                    info.animated = true;
+                    break;
+                }
+                let Some(&flags) = content.get(i + 9) else {
+                    break;
+                };
+                i += 10;
+                if flags & 0x80 != 0 {
+                    i += 3 << ((flags & 0x07) + 1);
+                }
+                i = gif_skip_sub_blocks(content, i + 1);
+            }
+            _ => break,
+        }
+    }
+    Some(info)
+}
+
+// gif_skip_sub_blocks returns the index following the data sub-blocks at i.
+fn gif_skip_sub_blocks(content: &[u8], mut i: usize) -> usize {
+    while let Some(&len) = content.get(i) {
+        i += 1 + len as usize;
+        if len == 0 {
+            break;
+        }
+    }
+    i
+}
+
+// https://developers.google.com/speed/webp/docs/riff_container
+fn webp_info(content: &[u8]) -> Option<ImageInfo> {
+    let mut info: Option<ImageInfo> = None;
+    let (mut alpha, mut orientation) = (false, None);
+    let mut chunks = content.get(12..)?;
+    while chunks.len() >= 8 {
+        let len = LittleEndian::read_u32(&chunks[4..8]) as usize;
+        let data = &chunks[8..(8 + len).min(chunks.len())];
+        match &chunks[..4] {
+            b"VP8X" if data.len() >= 10 => {
+                let mut i = ImageInfo::new(
+                    LittleEndian::read_u24(&data[4..7]) + 1,
+                    LittleEndian::read_u24(&data[7..10]) + 1,
+                );
+                i.animated = data[0] & 0x02 != 0;
+                alpha = data[0] & 0x10 != 0;
+                info = Some(i);
+            }
+            b"VP8 " if data.len() >= 10 && data[3..6] == [0x9D, 0x01, 0x2A] => {
+                info.get_or_insert(ImageInfo::new(
+                    (LittleEndian::read_u16(&data[6..8]) & 0x3FFF) as u32,
+                    (LittleEndian::read_u16(&data[8..10]) & 0x3FFF) as u32,
+                ));
+            }
+            b"VP8L" if data.len() >= 5 && data[0] == 0x2F => {
+                let bits = LittleEndian::read_u32(&data[1..5]);
+                info.get_or_insert(ImageInfo::new(
+                    (bits & 0x3FFF) + 1,
+                    ((bits >> 14) & 0x3FFF) + 1,
+                ));
+                alpha |= (bits >> 28) & 1 != 0;
+            }
+            b"ALPH" => alpha = true,
+            b"EXIF" => {
+                orientation = exif_orientation(data.strip_prefix(b"Exif\0\0").unwrap_or(data))
+            }
+            _ => {}
+        }
+        // Chunks are padded to an even size.
+        chunks = chunks.get(8 + len + len % 2..).unwrap_or(&[]);
+    }
+    info.map(|mut i| {
+        i.bit_depth = Some(8);
+        i.color_type = Some(if alpha {
+            ColorType::Rgba
+        } else {
+            ColorType::Rgb
+        });
+        i.orientation = orientation;
+        i
+    })
+}
+
+// https://learn.microsoft.com/en-us/windows/win32/gdi/bitmap-storage
+fn bmp_info(content: &[u8]) -> Option<ImageInfo> {
+    let header_size = LittleEndian::read_u32(content.get(14..18)?);
+    let (width, height, bpp) = if header_size == 12 {
+        // BITMAPCOREHEADER
+        let h = content.get(18..26)?;
+        (
+            LittleEndian::read_u16(&h[0..2]) as u32,
+            LittleEndian::read_u16(&h[2..4]) as u32,
+            LittleEndian::read_u16(&h[6..8]),
+        )
+    } else {
+        // BITMAPINFOHEADER and its successors. A negative height marks a
+        // top-down bitmap.
+        let h = content.get(18..30)?;
+        (
+            LittleEndian::read_i32(&h[0..4]).unsigned_abs(),
+            LittleEndian::read_i32(&h[4..8]).unsigned_abs(),
+            LittleEndian::read_u16(&h[10..12]),
+        )
+    };
+    let mut info = ImageInfo::new(width, height);
+    (info.bit_depth, info.color_type) = match bpp {
+        1 | 2 | 4 | 8 => (Some(bpp as u8), Some(ColorType::Indexed)),
+        16 => (None, Some(ColorType::Rgb)),
+        24 => (Some(8), Some(ColorType::Rgb)),
+        32 => (Some(8), Some(ColorType::Rgba)),
+        _ => (None, None),
+    };
+    Some(info)
+}
+
+// heif_info reads the image properties of an AVIF or HEIC file: the spatial
+// extent, pixel information, rotation and alpha plane of its items.
+// https://nokiatech.github.io/heif/technical.html
+fn heif_info(content: &[u8], sequence: bool) -> Option<ImageInfo> {
+    let brand = content.get(8..12)?;
+    let meta = Boxes::new(content).find(|(t, _)| *t == b"meta")?.1;
+    // meta is a full box: skip its version and flags.
+    let iprp = Boxes::new(meta.get(4..)?).find(|(t, _)| *t == b"iprp")?.1;
+    let ipco = Boxes::new(iprp).find(|(t, _)| *t == b"ipco")?.1;
+
+    let mut info: Option<ImageInfo> = None;
+    let (mut channels, mut alpha, mut rotation) = (None, false, None);
+    for (typ, data) in Boxes::new(ipco) {
+        match typ {
+            // The primary image is the largest one; others are thumbnails,
+            // alpha planes or tiles.
+            b"ispe" if data.len() >= 12 => {
+                let (w, h) = (
+                    BigEndian::read_u32(&data[4..8]),
+                    BigEndian::read_u32(&data[8..12]),
+                );
+                if info.as_ref().map_or(true, |i| {
+                    (w as u64) * (h as u64) > (i.width as u64) * (i.height as u64)
+                }) {
+                    info = Some(ImageInfo::new(w, h));
+                }
+            }
+            b"pixi" if data.len() >= 6 => channels = Some((data[4], data[5])),
+            b"auxC" => alpha |= data.ends_with(b"alpha\0") || data.ends_with(b"alpha"),
+            b"irot" if !data.is_empty() => rotation = Some(data[0] & 0x03),
+            _ => {}
+        }
+    }
+    info.map(|mut i| {
+        if let Some((n, bits)) = channels {
+            i.bit_depth = Some(bits);
+            i.color_type = match (n, alpha) {
+                (1, false) => Some(ColorType::Grayscale),
+                (1, true) => Some(ColorType::GrayscaleAlpha),
+                (3, false) => Some(ColorType::Rgb),
+                (3, true) => Some(ColorType::Rgba),
+                _ => None,
+            };
+        }
+        i.animated = sequence || brand == b"avis" || brand == b"msf1" || brand == b"hevs";
+        // irot rotates anti-clockwise by steps of 90 degrees.
+        i.orientation = rotation.map(|r| [1, 8, 3, 6][r as usize]);
+        i
+    })
+}
+
+// exif_orientation reads the orientation tag from the first IFD of EXIF
+// metadata, stored as a TIFF file.
+fn exif_orientation(tiff: &[u8]) -> Option<u8> {
+    let little = match tiff.get(..4)? {
+        b"II*\0" => true,
+        b"MM\0*" => false,
+        _ => return None,
+    };
+    let u16_at = |b: &[u8]| match little {
+        true => LittleEndian::read_u16(b),
+        false => BigEndian::read_u16(b),
+    };
+    let u32_at = |b: &[u8]| match little {
+        true => LittleEndian::read_u32(b),
+        false => BigEndian::read_u32(b),
+    };
+    let ifd = u32_at(tiff.get(4..8)?) as usize;
+    let count = u16_at(tiff.get(ifd..ifd + 2)?) as usize;
+    for n in 0..count {
+        let entry = tiff.get(ifd + 2 + n * 12..ifd + 14 + n * 12)?;
+        if u16_at(&entry[..2]) == 0x0112 {
+            let orientation = u16_at(&entry[8..10]);
+            return (1..=8).contains(&orientation).then_some(orientation as u8);
+        }
+    }
+    None
+}
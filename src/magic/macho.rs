@@ -0,0 +1,140 @@
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+
+use crate::mime::MimeDetector;
+
+// Mach-O files start with a header in the byte order of their architecture,
+// universal binaries with a big-endian list of the architectures they hold.
+// https://github.com/apple-oss-distributions/xnu/blob/main/EXTERNAL_HEADERS/mach-o/loader.h
+// https://github.com/apple-oss-distributions/xnu/blob/main/EXTERNAL_HEADERS/mach-o/fat.h
+const FAT_MAGIC: u32 = 0xCAFEBABE;
+const FAT_MAGIC_64: u32 = 0xCAFEBABF;
+const MH_MAGIC: u32 = 0xFEEDFACE;
+const MH_MAGIC_64: u32 = 0xFEEDFACF;
+
+const CPU_ARCH_ABI64: u32 = 0x01000000;
+const CPU_ARCH_ABI64_32: u32 = 0x02000000;
+
+// Java class files share the magic of universal binaries. Their major version
+// at the place of the architecture count is 45 or more; no universal binary
+// holds that many architectures.
+const MAX_FAT_ARCHES: u32 = 30;
+
+// fat_arches returns the CPU type and subtype of the architectures held by a
+// universal binary. The offset of each architecture must be aligned as the
+// entry states, and must not overlap the architecture list.
+fn fat_arches(content: &[u8]) -> Option<Vec<(u32, u32)>> {
+    if content.len() < 8 {
+        return None;
+    }
+    let entry_len = match BigEndian::read_u32(&content[..4]) {
+        FAT_MAGIC => 20,
+        FAT_MAGIC_64 => 32,
+        _ => return None,
+    };
+    let n = BigEndian::read_u32(&content[4..8]);
+    if n == 0 || n > MAX_FAT_ARCHES {
+        return None;
+    }
+    let list_end = 8 + n as u64 * entry_len as u64;
+    let mut arches = vec![];
+    // Entries out of content are not checked.
+    for entry in content[8..].chunks_exact(entry_len).take(n as usize) {
+        let (cpu, subtype) = (
+            BigEndian::read_u32(&entry[..4]),
+            BigEndian::read_u32(&entry[4..8]),
+        );
+        let (offset, align) = if entry_len == 20 {
+            (
+                BigEndian::read_u32(&entry[8..12]) as u64,
+                BigEndian::read_u32(&entry[16..20]),
+            )
+        } else {
+            (
+                BigEndian::read_u64(&entry[8..16]),
+                BigEndian::read_u32(&entry[24..28]),
+            )
+        };
+        if offset < list_end || align > 20 || offset % (1 << align) != 0 {
+            return None;
+        }
+        arches.push((cpu, subtype));
+    }
+    Some(arches)
+}
+
+// is_fat tells a universal binary apart from a Java class file.
+pub(crate) fn is_fat(content: &[u8]) -> bool {
+    fat_arches(content).is_some()
+}
+
+// thin_header returns the CPU type, subtype and file type of a single
+// architecture Mach-O file.
+fn thin_header(content: &[u8]) -> Option<(u32, u32, u32)> {
+    let header = content.get(..16)?;
+    let read: fn(&[u8]) -> u32 = match BigEndian::read_u32(&header[..4]) {
+        MH_MAGIC | MH_MAGIC_64 => BigEndian::read_u32,
+        m if m.swap_bytes() == MH_MAGIC || m.swap_bytes() == MH_MAGIC_64 => LittleEndian::read_u32,
+        _ => return None,
+    };
+    Some((
+        read(&header[4..8]),
+        read(&header[8..12]),
+        read(&header[12..16]),
+    ))
+}
+
+// arch_name returns the name of a CPU type and subtype as used by Apple tools.
+fn arch_name(cpu: u32, subtype: u32) -> Option<&'static str> {
+    // The upper bits of the subtype are capability flags.
+    let subtype = subtype & 0x00FFFFFF;
+    Some(match cpu {
+        7 => "i386",
+        c if c == 7 | CPU_ARCH_ABI64 && subtype == 8 => "x86_64h",
+        c if c == 7 | CPU_ARCH_ABI64 => "x86_64",
+        12 => "arm",
+        c if c == 12 | CPU_ARCH_ABI64 && subtype == 2 => "arm64e",
+        c if c == 12 | CPU_ARCH_ABI64 => "arm64",
+        c if c == 12 | CPU_ARCH_ABI64_32 => "arm64_32",
+        18 => "ppc",
+        c if c == 18 | CPU_ARCH_ABI64 => "ppc64",
+        _ => return None,
+    })
+}
+
+// macho_params reports the architectures of a Mach-O file.
+pub(crate) fn macho_params(content: &[u8]) -> Vec<(&'static str, String)> {
+    let arches: Vec<&str> = match fat_arches(content) {
+        Some(arches) => arches
+            .into_iter()
+            .filter_map(|(cpu, subtype)| arch_name(cpu, subtype))
+            .collect(),
+        None => thin_header(content)
+            .and_then(|(cpu, subtype, _)| arch_name(cpu, subtype))
+            .into_iter()
+            .collect(),
+    };
+    if arches.is_empty() {
+        return vec![];
+    }
+    vec![("arch", arches.join(", "))]
+}
+
+// MachO matches Mach-O binaries format.
+pub(crate) struct MachODetector;
+
+impl MimeDetector for MachODetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        is_fat(content) || thin_header(content).is_some()
+    }
+}
+
+// MachOFileType matches a single architecture Mach-O file of the given type.
+pub(crate) struct MachOFileTypeDetector {
+    pub file_type: u32,
+}
+
+impl MimeDetector for MachOFileTypeDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        thin_header(content).is_some_and(|(_, _, t)| t == self.file_type)
+    }
+}
@@ -1,7 +1,20 @@
 pub(crate) mod archive;
+pub(crate) mod asn1;
 pub(crate) mod audio;
 pub(crate) mod base;
 pub(crate) mod binary;
+pub(crate) mod bytecode;
+pub(crate) mod disk;
+pub(crate) mod ebml;
+pub(crate) mod elf;
 pub(crate) mod ftyp;
 pub(crate) mod image;
+pub(crate) mod macho;
+pub(crate) mod pe;
+pub(crate) mod riff;
 pub(crate) mod text;
+pub(crate) mod text_csv;
+pub(crate) mod text_key;
+pub(crate) mod text_mail;
+pub(crate) mod text_markup;
+pub(crate) mod text_source;
@@ -0,0 +1,211 @@
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::mime::MimeDetector;
+
+// Pe holds the fields of a Portable Executable header telling what kind of
+// program a file is.
+// https://learn.microsoft.com/en-us/windows/win32/debug/pe-format
+struct Pe {
+    machine: u16,
+    characteristics: u16,
+    subsystem: u16,
+    // Whether the file has a CLR runtime header, i.e. is a .NET assembly.
+    clr: bool,
+    // The version of the ReadyToRun header of .NET assemblies precompiled to
+    // native code, when the headers are within content.
+    ready_to_run: Option<(u16, u16)>,
+}
+
+const IMAGE_FILE_DLL: u16 = 0x2000;
+const IMAGE_SUBSYSTEM_NATIVE: u16 = 1;
+
+// new_header_offset returns the offset of the new executable header, stored
+// at 0x3C in the MZ header.
+fn new_header_offset(content: &[u8]) -> Option<usize> {
+    if !content.starts_with(b"MZ") || content.len() < 0x40 {
+        return None;
+    }
+    Some(LittleEndian::read_u32(&content[0x3C..0x40]) as usize)
+}
+
+// pe parses the COFF and optional headers of a PE file. Returns None when the
+// headers are not within content.
+fn pe(content: &[u8]) -> Option<Pe> {
+    let offset = new_header_offset(content)?;
+    let header = content.get(offset..)?;
+    if !header.starts_with(b"PE\0\0") || header.len() < 24 {
+        return None;
+    }
+    let coff = &header[4..24];
+    let opt_size = LittleEndian::read_u16(&coff[16..18]) as usize;
+    let opt = header.get(24..24 + opt_size)?;
+    // The data directories come after 96 bytes of fields in PE32 and 112 in
+    // PE32+, which has 64 bits wide image base and stack and heap sizes.
+    let dirs = match opt.get(..2) {
+        Some([0x0B, 0x01]) => 96,
+        Some([0x0B, 0x02]) => 112,
+        _ => return None,
+    };
+    let subsystem = LittleEndian::read_u16(opt.get(68..70)?);
+    // The CLR runtime header is the 15th data directory, each 8 bytes long.
+    let clr_rva = opt
+        .get(dirs + 14 * 8..dirs + 14 * 8 + 4)
+        .map_or(0, LittleEndian::read_u32);
+    let sections = header
+        .get(24 + opt_size..)
+        .unwrap_or_default()
+        .chunks_exact(40)
+        .take(LittleEndian::read_u16(&coff[2..4]) as usize)
+        .collect::<Vec<_>>();
+    Some(Pe {
+        machine: LittleEndian::read_u16(&coff[..2]),
+        characteristics: LittleEndian::read_u16(&coff[18..20]),
+        subsystem,
+        clr: clr_rva != 0,
+        ready_to_run: ready_to_run(content, &sections, clr_rva),
+    })
+}
+
+// rva_to_offset maps a relative virtual address to a file offset, using the
+// section headers.
+fn rva_to_offset(sections: &[&[u8]], rva: u32) -> Option<usize> {
+    sections.iter().find_map(|s| {
+        let (size, addr, raw) = (
+            LittleEndian::read_u32(&s[16..20]),
+            LittleEndian::read_u32(&s[12..16]),
+            LittleEndian::read_u32(&s[20..24]),
+        );
+        let delta = rva.checked_sub(addr).filter(|&d| d < size)?;
+        Some(raw as usize + delta as usize)
+    })
+}
+
+// ready_to_run reads the version of the ReadyToRun header, pointed to by the
+// ManagedNativeHeader directory of the CLR runtime header.
+// https://github.com/dotnet/runtime/blob/main/docs/design/coreclr/botr/readytorun-format.md
+fn ready_to_run(content: &[u8], sections: &[&[u8]], clr_rva: u32) -> Option<(u16, u16)> {
+    if clr_rva == 0 {
+        return None;
+    }
+    let cor = rva_to_offset(sections, clr_rva)?;
+    let native_rva = LittleEndian::read_u32(content.get(cor + 64..cor + 68)?);
+    if native_rva == 0 {
+        return None;
+    }
+    let r2r = rva_to_offset(sections, native_rva)?;
+    let r2r = content.get(r2r..r2r + 8)?;
+    (&r2r[..4] == b"RTR\0").then(|| {
+        (
+            LittleEndian::read_u16(&r2r[4..6]),
+            LittleEndian::read_u16(&r2r[6..8]),
+        )
+    })
+}
+
+// pe_params reports the kind, machine type and subsystem of a PE file, and the
+// CLR runtime and ReadyToRun version for .NET assemblies.
+pub(crate) fn pe_params(content: &[u8]) -> Vec<(&'static str, String)> {
+    let Some(pe) = pe(content) else {
+        return vec![];
+    };
+    // Executables, libraries and drivers share the MIME type; only the
+    // extension would tell them apart otherwise.
+    let kind = match pe.subsystem {
+        IMAGE_SUBSYSTEM_NATIVE | 11 | 12 => "driver",
+        _ if pe.characteristics & IMAGE_FILE_DLL != 0 => "dll",
+        _ => "exe",
+    };
+    let mut params = vec![("kind", kind.to_string())];
+    let machine = match pe.machine {
+        0x014C => Some("x86"),
+        0x8664 => Some("x64"),
+        0xAA64 => Some("arm64"),
+        0x01C0 | 0x01C2 | 0x01C4 => Some("arm"),
+        0x0200 => Some("ia64"),
+        0x0EBC => Some("ebc"),
+        0x5064 => Some("riscv64"),
+        0x6264 => Some("loongarch64"),
+        _ => None,
+    };
+    if let Some(m) = machine {
+        params.push(("machine", m.to_string()));
+    }
+    let subsystem = match pe.subsystem {
+        1 => Some("native"),
+        2 => Some("gui"),
+        3 => Some("console"),
+        9 => Some("windows-ce"),
+        10 => Some("efi-application"),
+        11 => Some("efi-boot-driver"),
+        12 => Some("efi-runtime-driver"),
+        13 => Some("efi-rom"),
+        16 => Some("boot-application"),
+        _ => None,
+    };
+    if let Some(s) = subsystem {
+        params.push(("subsystem", s.to_string()));
+    }
+    if pe.clr {
+        params.push(("runtime", "clr".to_string()));
+    }
+    if let Some((major, minor)) = pe.ready_to_run {
+        params.push(("readytorun", format!("{}.{}", major, minor)));
+    }
+    params
+}
+
+// DosExe matches an MS-DOS executable without a new executable header, PE or
+// the older NE, LE and LX formats.
+pub(crate) struct DosExeDetector;
+
+impl MimeDetector for DosExeDetector {
+    fn detect(&self, content: &[u8], limit: usize) -> bool {
+        let whole = limit == 0 || content.len() < limit;
+        if !content.starts_with(b"MZ") {
+            return false;
+        }
+        // Programs with a new executable header put their relocation table,
+        // whose offset is at 0x18, after the 0x40 bytes long extended MZ header.
+        // DOS programs may keep anything at 0x3C, e.g. code or relocations.
+        let Some(header) = content.get(..0x40) else {
+            return whole;
+        };
+        if LittleEndian::read_u16(&header[0x18..0x1A]) < 0x40 {
+            return true;
+        }
+        let offset = LittleEndian::read_u32(&header[0x3C..0x40]) as usize;
+        match content.get(offset..offset.saturating_add(2)) {
+            Some(s) => !matches!(s, b"PE" | b"NE" | b"LE" | b"LX"),
+            // The new header may be beyond the limit, but not beyond the file.
+            None => whole,
+        }
+    }
+}
+
+// Efi matches a UEFI application or driver.
+pub(crate) struct EfiDetector;
+
+impl MimeDetector for EfiDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        pe(content).is_some_and(|pe| (10..=13).contains(&pe.subsystem))
+    }
+}
+
+// PeDriver matches a Windows kernel-mode driver. Drivers run in the native
+// subsystem, like the few user-mode programs started before Win32.
+pub(crate) struct PeDriverDetector;
+
+impl MimeDetector for PeDriverDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        pe(content).is_some_and(|pe| pe.subsystem == IMAGE_SUBSYSTEM_NATIVE)
+    }
+}
+
+// Dll matches a Windows dynamic-link library.
+pub(crate) struct DllDetector;
+
+impl MimeDetector for DllDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        pe(content).is_some_and(|pe| pe.characteristics & IMAGE_FILE_DLL != 0)
+    }
+}
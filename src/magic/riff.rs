@@ -0,0 +1,130 @@
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+
+use crate::mime::MimeDetector;
+
+// Riff is the header of a Resource Interchange File Format container: the
+// container ID, e.g. "RIFF" or its big-endian counterpart "RIFX", and the form
+// type telling what the chunks hold, e.g. "WAVE" or "AVI ".
+// https://www.loc.gov/preservation/digital/formats/fdd/fdd000025.shtml
+struct Riff<'a> {
+    id: &'a [u8],
+    form: &'a [u8],
+}
+
+// riff parses the header of a RIFF container. RF64 and BW64 are RIFF variants
+// with 64 bits sizes, used for WAV files larger than 4 GiB.
+// https://tech.ebu.ch/docs/tech/tech3306v1_1.pdf
+fn riff(content: &[u8]) -> Option<Riff<'_>> {
+    if content.len() < 12 {
+        return None;
+    }
+    let id = &content[..4];
+    matches!(id, b"RIFF" | b"RIFX" | b"RF64" | b"BW64").then(|| Riff {
+        id,
+        form: &content[8..12],
+    })
+}
+
+// Chunks iterates over the chunks of a RIFF container, yielding their ID and
+// data. The data is cut short when the content is truncated.
+struct Chunks<'a> {
+    data: &'a [u8],
+    big_endian: bool,
+}
+
+impl<'a> Iterator for Chunks<'a> {
+    type Item = (&'a [u8], &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let data = self.data;
+        if data.len() < 8 {
+            return None;
+        }
+        let size = if self.big_endian {
+            BigEndian::read_u32(&data[4..8])
+        } else {
+            LittleEndian::read_u32(&data[4..8])
+        } as usize;
+        let end = 8usize.saturating_add(size).min(data.len());
+        // Chunks are padded to an even size.
+        self.data = &data[(end + size % 2).min(data.len())..];
+        Some((&data[..4], &data[8..end]))
+    }
+}
+
+fn chunks(content: &[u8]) -> Chunks<'_> {
+    Chunks {
+        data: content.get(12..).unwrap_or_default(),
+        big_endian: content.starts_with(b"RIFX"),
+    }
+}
+
+// Riff matches any RIFF container.
+pub(crate) struct RiffDetector;
+
+impl MimeDetector for RiffDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        riff(content).is_some()
+    }
+}
+
+// RiffForm matches a RIFF container of the given form type.
+pub(crate) struct RiffFormDetector {
+    pub form: &'static [u8],
+}
+
+impl MimeDetector for RiffFormDetector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        riff(content).is_some_and(|r| r.form == self.form)
+    }
+}
+
+// Rf64 matches a RIFF container with 64 bits sizes.
+pub(crate) struct Rf64Detector;
+
+impl MimeDetector for Rf64Detector {
+    fn detect(&self, content: &[u8], _: usize) -> bool {
+        riff(content).is_some_and(|r| r.id == b"RF64" || r.id == b"BW64")
+    }
+}
+
+// wav_params reports the codec of a WAV file, from the format tag of its
+// fmt chunk.
+// https://www.rfc-editor.org/rfc/rfc2361.html
+pub(crate) fn wav_params(content: &[u8]) -> Vec<(&'static str, String)> {
+    let Some((_, fmt)) = chunks(content).find(|(id, _)| *id == b"fmt ") else {
+        return vec![];
+    };
+    let read_u16 = |b: &[u8]| {
+        if content.starts_with(b"RIFX") {
+            BigEndian::read_u16(b)
+        } else {
+            LittleEndian::read_u16(b)
+        }
+    };
+    let mut tag = match fmt.get(..2) {
+        Some(b) => read_u16(b),
+        None => return vec![],
+    };
+    // WAVE_FORMAT_EXTENSIBLE keeps the actual format tag at the start of the
+    // sub-format GUID.
+    if tag == 0xFFFE {
+        match fmt.get(24..26) {
+            Some(b) => tag = read_u16(b),
+            None => return vec![],
+        }
+    }
+    let codec = match tag {
+        0x0001 => "pcm",
+        0x0002 => "adpcm",
+        0x0003 => "float",
+        0x0006 => "alaw",
+        0x0007 => "mulaw",
+        0x0011 => "ima-adpcm",
+        0x0031 => "gsm",
+        0x0050 => "mpeg",
+        0x0055 => "mp3",
+        _ => return vec![],
+    };
+    vec![("codec", codec.to_string())]
+}
@@ -1,10 +1,13 @@
 use std::sync::LazyLock;
 
-use serde_json::Value;
+use serde::de::IgnoredAny;
 
 use crate::mime::MimeDetector;
 
-use super::base::{trim_left_ws, BytesExt, CiPrefixDetector, SheBangDetector};
+use super::{
+    asn1::is_pkcs7,
+    base::{trim_left_ws, trim_right_ws, BytesExt, CiPrefixDetector, SheBangDetector},
+};
 
 #[derive(Debug)]
 struct Bom {
@@ -73,7 +76,7 @@ pub(crate) struct PhpDetector;
 impl MimeDetector for PhpDetector {
     fn detect(&self, content: &[u8], limit: usize) -> bool {
         let php_page_f = SheBangDetector {
-            sigs: vec![b"/usr/local/bin/php", b"/usr/bin/php", b"/usr/bin/env php"],
+            interpreters: vec!["php"],
         };
         if php_page_f.detect(content, limit) {
             return true;
@@ -90,6 +93,7 @@ pub(crate) struct JsonDetector;
 
 impl MimeDetector for JsonDetector {
     fn detect(&self, content: &[u8], limit: usize) -> bool {
+        let truncated = limit > 0 && content.len() >= limit;
         let content = trim_left_ws(content);
         // #175 A single JSON string, number or bool is not considered JSON.
         // JSON objects and arrays are reported as JSON.
@@ -97,133 +101,494 @@ impl MimeDetector for JsonDetector {
             return false;
         }
 
-        let parsed = serde_json::from_slice::<Value>(content);
-        if limit == 0 || content.len() < limit {
-            return parsed.is_ok();
+        match serde_json::from_slice::<IgnoredAny>(content) {
+            Ok(_) => true,
+            // If a section of the file was provided, the only acceptable error
+            // is running out of input in the middle of the value.
+            Err(e) => truncated && e.is_eof(),
         }
+    }
+}
 
-        match parsed {
-            Ok(_) => true,
-            Err(e) => {
-                // If a section of the file was provided, check if all of it was parsed.
-                if e.is_eof() && !content.is_empty() {
-                    return true;
+// NdJson matches a Newline delimited JSON file. The content must hold more than
+// one complete JSON value, consecutive values must be separated by a newline and
+// at least one of them must be an object or an array.
+pub(crate) struct NdJsonDetector;
+
+impl MimeDetector for NdJsonDetector {
+    fn detect(&self, content: &[u8], limit: usize) -> bool {
+        let truncated = limit > 0 && content.len() >= limit;
+        let mut stream = serde_json::Deserializer::from_slice(content).into_iter::<IgnoredAny>();
+        let (mut count, mut has_obj_or_arr) = (0, false);
+        loop {
+            let start = stream.byte_offset();
+            match stream.next() {
+                None => break,
+                Some(Ok(_)) => {}
+                // The last value of a partial file is allowed to be cut short.
+                Some(Err(e)) => {
+                    if truncated && e.is_eof() {
+                        break;
+                    }
+                    return false;
                 }
-                true
             }
+            let raw = &content[start..stream.byte_offset()];
+            let value = trim_left_ws(raw);
+            // The whitespace preceding a value separates it from the previous one.
+            if count > 0 && !raw[..raw.len() - value.len()].contains(&b'\n') {
+                return false;
+            }
+            if value[0] == b'{' || value[0] == b'[' {
+                has_obj_or_arr = true;
+            }
+            count += 1;
+        }
+
+        count > 1 && has_obj_or_arr
+    }
+}
+
+// json_root_entries scans the root JSON object and returns its keys along with
+// the raw bytes of their values. Scanning stops at the end of the input, so a
+// truncated object yields the entries seen so far, the last value possibly cut short.
+fn json_root_entries(content: &[u8]) -> Option<Vec<(String, &[u8])>> {
+    let content = trim_left_ws(content);
+    if content.first() != Some(&b'{') {
+        return None;
+    }
+    let mut entries = Vec::new();
+    let mut i = 1;
+    loop {
+        i += ws_len(&content[i..]);
+        match content.get(i) {
+            Some(b'"') => {}
+            Some(b'}') | None => return Some(entries),
+            Some(_) => return None,
+        }
+        let key_len = json_value_len(&content[i..]);
+        let Ok(key) = serde_json::from_slice::<String>(&content[i..i + key_len]) else {
+            // The key itself is cut short.
+            return Some(entries);
+        };
+        i += key_len;
+        i += ws_len(&content[i..]);
+        if content.get(i) != Some(&b':') {
+            return (i == content.len()).then_some(entries);
+        }
+        i += 1;
+        i += ws_len(&content[i..]);
+        let value_len = json_value_len(&content[i..]);
+        entries.push((key, &content[i..i + value_len]));
+        i += value_len;
+        i += ws_len(&content[i..]);
+        match content.get(i) {
+            Some(b',') => i += 1,
+            Some(b'}') | None => return Some(entries),
+            Some(_) => return None,
         }
     }
 }
 
+fn ws_len(content: &[u8]) -> usize {
+    content.len() - trim_left_ws(content).len()
+}
+
+// json_value_len returns the length of the JSON value content starts with,
+// without validating it. Strings, arrays and objects are skipped as a whole,
+// scalars end at the next delimiter.
+fn json_value_len(content: &[u8]) -> usize {
+    let (mut depth, mut in_string, mut escaped) = (0usize, false, false);
+    for (i, &b) in content.iter().enumerate() {
+        if in_string {
+            match b {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => {
+                    in_string = false;
+                    if depth == 0 {
+                        return i + 1;
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+        match b {
+            b'"' => in_string = true,
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' if depth == 0 => return i,
+            b'}' | b']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            b',' | b':' if depth == 0 => return i,
+            _ if depth == 0 && b.is_ascii_whitespace() => return i,
+            _ => {}
+        }
+    }
+    content.len()
+}
+
+// json_root_string returns the value of key in the root object if it is a string.
+fn json_root_string(content: &[u8], key: &str) -> Option<String> {
+    let entries = json_root_entries(content)?;
+    let (_, value) = entries.into_iter().find(|(k, _)| k == key)?;
+    serde_json::from_slice::<String>(value).ok()
+}
+
+// json_root_has_keys returns true if the root object holds all the keys.
+fn json_root_has_keys(content: &[u8], keys: &[&str]) -> bool {
+    let Some(entries) = json_root_entries(content) else {
+        return false;
+    };
+    keys.iter().all(|key| entries.iter().any(|(k, _)| k == key))
+}
+
 // GeoJSON matches a RFC 7946 GeoJSON file.
 //
-// GeoJSON detection implies searching for key:value pairs like: `"type": "Feature"`
-// in the input.
-// BUG(gabriel-vasile): The "type" key should be searched for in the root object.
+// GeoJSON detection implies checking the "type" key of the root object for
+// values like "Feature".
 pub(crate) struct GeoJsonDetector;
 
 impl MimeDetector for GeoJsonDetector {
     fn detect(&self, content: &[u8], _limit: usize) -> bool {
-        let mut content = trim_left_ws(content);
-        if content.is_empty() {
+        let Some(t) = json_root_string(content, "type") else {
             return false;
-        }
-        // GeoJSON is always a JSON object, not a JSON array or any other JSON value.
-        if content[0] != b'{' {
+        };
+        matches!(
+            t.as_str(),
+            "Feature"
+                | "FeatureCollection"
+                | "Point"
+                | "LineString"
+                | "Polygon"
+                | "MultiPoint"
+                | "MultiLineString"
+                | "MultiPolygon"
+                | "GeometryCollection"
+        )
+    }
+}
+
+// TopoJSON matches a TopoJSON file.
+// Spec: https://github.com/topojson/topojson-specification
+pub(crate) struct TopoJsonDetector;
+
+impl MimeDetector for TopoJsonDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        json_root_string(content, "type").is_some_and(|t| t == "Topology")
+    }
+}
+
+// HAR matches a HAR Spec file.
+// Spec: http://www.softwareishard.com/blog/har-12-spec/
+pub(crate) struct HarDetector;
+
+impl MimeDetector for HarDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        let Some(entries) = json_root_entries(content) else {
             return false;
-        }
-        let s = br#""type""#;
-        let (si, sl) = (content.index(s), s.len());
-        let Some(si) = si else {
+        };
+        let Some((_, log)) = entries.into_iter().find(|(k, _)| k == "log") else {
             return false;
         };
-        // If the "type" string is the suffix of the input,
-        // there is no need to search for the value of the key.
-        if si + sl == content.len() {
+        let Some(log) = json_root_entries(log) else {
             return false;
-        }
-        // Skip the "type" part.
-        content = &content[si + sl..];
-        // Skip any whitespace before the colon.
-        content = trim_left_ws(content);
-        // Check for colon.
-        if content.is_empty() || content[0] != b':' {
+        };
+        log.iter()
+            .any(|(k, _)| k == "version" || k == "creator" || k == "entries")
+    }
+}
+
+// JsonSchema matches a JSON Schema file, recognized by its "$schema" URI.
+pub(crate) struct JsonSchemaDetector;
+
+impl MimeDetector for JsonSchemaDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        json_root_string(content, "$schema").is_some_and(|s| s.contains("json-schema.org"))
+    }
+}
+
+// OpenApi matches an OpenAPI 3.x or Swagger 2.0 description file.
+pub(crate) struct OpenApiDetector;
+
+impl MimeDetector for OpenApiDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        json_root_string(content, "openapi").is_some_and(|v| v.starts_with("3."))
+            || json_root_string(content, "swagger").is_some_and(|v| v == "2.0")
+    }
+}
+
+// Ipynb matches a Jupyter notebook file.
+pub(crate) struct IpynbDetector;
+
+impl MimeDetector for IpynbDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        json_root_has_keys(content, &["cells", "nbformat"])
+            || json_root_has_keys(content, &["cells", "metadata"])
+    }
+}
+
+// Gltf matches a glTF model file in its JSON representation.
+// Spec: https://registry.khronos.org/glTF/specs/2.0/glTF-2.0.html
+pub(crate) struct GltfDetector;
+
+impl MimeDetector for GltfDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        let Some(entries) = json_root_entries(content) else {
             return false;
+        };
+        let Some((_, asset)) = entries.iter().find(|(k, _)| k == "asset") else {
+            return false;
+        };
+        json_root_has_keys(asset, &["version"])
+    }
+}
+
+// WebExtensionManifest matches a Chrome or Firefox extension manifest.json file.
+pub(crate) struct WebExtensionManifestDetector;
+
+impl MimeDetector for WebExtensionManifestDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        json_root_has_keys(content, &["manifest_version", "name", "version"])
+    }
+}
+
+// WebAppManifest matches a Web Application Manifest file.
+// Spec: https://www.w3.org/TR/appmanifest/
+pub(crate) struct WebAppManifestDetector;
+
+impl MimeDetector for WebAppManifestDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        let Some(entries) = json_root_entries(content) else {
+            return false;
+        };
+        let has = |key: &str| entries.iter().any(|(k, _)| k == key);
+        (has("name") || has("short_name"))
+            && (has("start_url") || has("display") || has("icons") || has("scope"))
+    }
+}
+
+// JsonLd matches a JSON-LD document, an object with a "@context" key.
+pub(crate) struct JsonLdDetector;
+
+impl MimeDetector for JsonLdDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        json_root_has_keys(content, &["@context"])
+    }
+}
+
+// ActivityStreams matches an Activity Streams 2.0 document: JSON-LD using the
+// ActivityStreams context.
+pub(crate) struct ActivityStreamsDetector;
+
+impl MimeDetector for ActivityStreamsDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        let Some(entries) = json_root_entries(content) else {
+            return false;
+        };
+        entries.iter().any(|(k, v)| {
+            k == "@context" && v.index(b"https://www.w3.org/ns/activitystreams").is_some()
+        })
+    }
+}
+
+// Svg matches a SVG file.
+pub(crate) struct SvgDetector;
+
+impl MimeDetector for SvgDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        let s = b"<svg";
+        content.windows(s.len()).any(|window| window == s)
+    }
+}
+
+// VCard matches a Virtual Contact File.
+pub(crate) struct VCardDetector;
+
+impl MimeDetector for VCardDetector {
+    fn detect(&self, content: &[u8], limit: usize) -> bool {
+        CiPrefixDetector {
+            sigs: vec![b"BEGIN:VCARD\n", b"BEGIN:VCARD\r\n"],
+        }
+        .detect(skip_utf8_bom(content), limit)
+    }
+}
+
+// vcard_params reports the version of a vCard: 2.1, 3.0 or 4.0.
+pub(crate) fn vcard_params(content: &[u8]) -> Vec<(&'static str, String)> {
+    for line in skip_utf8_bom(content).split(|&b| b == b'\n') {
+        let line = trim_right_ws(line);
+        if line.len() < 8 || !line[..8].eq_ignore_ascii_case(b"VERSION:") {
+            continue;
+        }
+        let version = trim_left_ws(&line[8..]);
+        if version == b"2.1" || version == b"3.0" || version == b"4.0" {
+            return vec![("version", String::from_utf8_lossy(version).into_owned())];
         }
+        break;
+    }
+    vec![]
+}
 
-        // Skip any whitespace after the colon.
-        content = trim_left_ws(&content[1..]);
-
-        let geo_jsontypes: Vec<&[u8]> = vec![
-            br#""Feature""#,
-            br#""FeatureCollection""#,
-            br#""Point""#,
-            br#""LineString""#,
-            br#""Polygon""#,
-            br#""MultiPoint""#,
-            br#""MultiLineString""#,
-            br#""MultiPolygon""#,
-            br#""GeometryCollection""#,
-        ];
-        for t in geo_jsontypes {
-            if content.starts_with(t) {
-                return true;
-            }
+// ICalendar matches a iCalendar file.
+pub(crate) struct ICalendarDetector;
+
+impl MimeDetector for ICalendarDetector {
+    fn detect(&self, content: &[u8], limit: usize) -> bool {
+        CiPrefixDetector {
+            sigs: vec![b"BEGIN:VCALENDAR\n", b"BEGIN:VCALENDAR\r\n"],
         }
-        false
+        .detect(skip_utf8_bom(content), limit)
     }
 }
 
-// HAR matches a HAR Spec file.
-// Spec: http://www.softwareishard.com/blog/har-12-spec/
-pub(crate) struct HarDetector;
+// json_array_tag returns true if content is a JSON array whose first element is
+// the string tag, the layout used by jCard and jCal.
+fn json_array_tag(content: &[u8], tag: &[u8]) -> bool {
+    let content = trim_left_ws(content);
+    if content.first() != Some(&b'[') {
+        return false;
+    }
+    let content = trim_left_ws(&content[1..]);
+    if content.len() < tag.len() + 2 || content[0] != b'"' || &content[1..tag.len() + 1] != tag {
+        return false;
+    }
+    let content = &content[tag.len() + 1..];
+    content[0] == b'"' && trim_left_ws(&content[1..]).starts_with(b",")
+}
 
-impl MimeDetector for HarDetector {
+// JCard matches a jCard (RFC 7095) file, the JSON representation of a vCard.
+pub(crate) struct JCardDetector;
+
+impl MimeDetector for JCardDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        json_array_tag(content, b"vcard")
+    }
+}
+
+// JCal matches a jCal (RFC 7265) file, the JSON representation of an iCalendar.
+pub(crate) struct JCalDetector;
+
+impl MimeDetector for JCalDetector {
     fn detect(&self, content: &[u8], _limit: usize) -> bool {
-        let s = br#""log""#;
-        let (si, sl) = (content.index(s), s.len());
+        json_array_tag(content, b"vcalendar")
+    }
+}
+
+// Vtt matches a Web Video Text Tracks (WebVTT) file. See
+// https://www.iana.org/assignments/media-types/text/vtt.
+pub(crate) struct VttDetector;
+
+impl MimeDetector for VttDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        // "WEBVTT" must be alone in the first line or followed by a space or tab.
+        let Some(rest) = skip_utf8_bom(content).strip_prefix(b"WEBVTT") else {
+            return false;
+        };
+        rest.is_empty() || matches!(rest[0], b'\n' | b'\r' | b' ' | b'\t')
+    }
+}
 
-        let Some(si) = si else {
+// Srt matches a SubRip file: a numbered cue, a line with the start and end
+// timestamps separated by an arrow and at least one line of subtitle text.
+pub(crate) struct SrtDetector;
+
+impl MimeDetector for SrtDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        let mut lines = trim_left_ws(skip_utf8_bom(content))
+            .split(|&b| b == b'\n')
+            .map(trim_right_ws);
+        let Some(index) = lines.next() else {
             return false;
         };
-        // If the "log" string is the suffix of the input,
-        // there is no need to search for the value of the key.
-        if si + sl == content.len() {
+        if index.is_empty() || !index.iter().all(u8::is_ascii_digit) {
             return false;
         }
-        // Skip the "log" part.
-        let mut content = &content[si + sl..];
-        // Skip any whitespace before the colon.
-        content = trim_left_ws(content);
-        // Check for colon.
-        if content.is_empty() || content[0] != b':' {
+        let Some(timing) = lines.next() else {
+            return false;
+        };
+        let Some(i) = timing.index(b" --> ") else {
+            return false;
+        };
+        // The end timestamp may be followed by display coordinates.
+        let end = &timing[i + 5..];
+        let end = &end[..end.iter().position(|&b| b == b' ').unwrap_or(end.len())];
+        let (Some(t0), Some(t1)) = (srt_timestamp(&timing[..i]), srt_timestamp(end)) else {
+            return false;
+        };
+        if t0 > t1 {
             return false;
         }
-        // Skip any whitespace after the colon.
-        content = trim_left_ws(&content[1..]);
+        // A third line must exist and not be empty. This is the actual subtitle text.
+        lines.next().is_some_and(|l| !l.is_empty())
+    }
+}
 
-        let har_jsontypes: Vec<&[u8]> = vec![br#""version""#, br#""creator""#, br#""entries""#];
-        for t in har_jsontypes {
-            let si = content.index(t);
-            if si.is_some() {
-                return true;
-            }
+// srt_timestamp parses a HH:MM:SS,mmm timestamp into milliseconds.
+fn srt_timestamp(t: &[u8]) -> Option<u64> {
+    let num = |s: &[u8], len: usize| {
+        if s.len() != len || !s.iter().all(u8::is_ascii_digit) {
+            return None;
         }
+        Some(s.iter().fold(0u64, |n, &d| n * 10 + (d - b'0') as u64))
+    };
+    let mut parts = t.split(|&b| b == b':');
+    let (Some(h), Some(m), Some(s), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return None;
+    };
+    // Hours have at least two digits, some writers use a period for the fraction.
+    if h.len() < 2 || s.len() != 6 || (s[2] != b',' && s[2] != b'.') {
+        return None;
+    }
+    let (h, m) = (num(h, h.len())?, num(m, 2)?);
+    let (s, ms) = (num(&s[..2], 2)?, num(&s[3..], 3)?);
+    if m > 59 || s > 59 {
+        return None;
+    }
+    Some(((h * 60 + m) * 60 + s) * 1000 + ms)
+}
 
-        false
+// Ssa matches a SubStation Alpha subtitle file.
+pub(crate) struct SsaDetector;
+
+impl MimeDetector for SsaDetector {
+    fn detect(&self, content: &[u8], limit: usize) -> bool {
+        CiPrefixDetector {
+            sigs: vec![b"[SCRIPT INFO]"],
+        }
+        .detect(trim_left_ws(skip_utf8_bom(content)), limit)
     }
 }
 
-// Svg matches a SVG file.
-pub(crate) struct SvgDetector;
+// Ass matches an Advanced SubStation Alpha subtitle file, a SubStation Alpha
+// file with script type "v4.00+" or a "[V4+ Styles]" section.
+pub(crate) struct AssDetector;
 
-impl MimeDetector for SvgDetector {
+impl MimeDetector for AssDetector {
     fn detect(&self, content: &[u8], _limit: usize) -> bool {
-        let s = b"<svg";
-        return content.windows(s.len()).any(|window| window == s);
+        for line in content.split(|&b| b == b'\n') {
+            let line = trim_left_ws(trim_right_ws(line));
+            if line.eq_ignore_ascii_case(b"[V4+ Styles]") {
+                return true;
+            }
+            if line.len() > 11 && line[..11].eq_ignore_ascii_case(b"ScriptType:") {
+                return trim_left_ws(&line[11..]).eq_ignore_ascii_case(b"v4.00+");
+            }
+        }
+        false
     }
 }
 
+fn skip_utf8_bom(content: &[u8]) -> &[u8] {
+    content.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(content)
+}
+
 // P7s matches an .p7s signature File (PEM, Base64).
 pub(crate) struct P7sDetector;
 
@@ -233,27 +598,7 @@ impl MimeDetector for P7sDetector {
         if content.starts_with(b"-----BEGIN PKCS7") {
             return true;
         }
-        // Check if DER Encoding is long enough.
-        if content.len() < 20 {
-            return false;
-        }
-        // Magic Bytes for the signedData ASN.1 encoding.
-        let start_header: Vec<&[u8]> = vec![
-            &[0x30, 0x80],
-            &[0x30, 0x81],
-            &[0x30, 0x82],
-            &[0x30, 0x83],
-            &[0x30, 0x84],
-        ];
-        let signed_data_match = &[0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x07];
-        // Check if Header is correct. There are multiple valid headers.
-        for (i, m) in start_header.iter().enumerate() {
-            // If first bytes match, then check for ASN.1 Object Type.
-            if content.starts_with(m) && content[i + 2..].starts_with(signed_data_match) {
-                return true;
-            }
-        }
-
-        false
+        // Check for DER Encoding.
+        is_pkcs7(content)
     }
 }
@@ -0,0 +1,217 @@
+use crate::mime::MimeDetector;
+
+use super::{base::drop_last_line, text::from_boom};
+
+/// The line terminator used between records of a delimiter separated file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineTerminator {
+    /// `\n`
+    Lf,
+    /// `\r\n`
+    CrLf,
+    /// `\r`
+    Cr,
+}
+
+/// CsvDialect describes how a delimiter separated values file is written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvDialect {
+    /// The byte separating the fields of a record, e.g. `,` or `\t`.
+    pub delimiter: u8,
+    /// The byte used to quote fields, `None` if no quoted field was seen.
+    pub quote: Option<u8>,
+    /// Whether the first record looks like a header row.
+    pub has_header: bool,
+    /// The line terminator used between records.
+    pub line_terminator: LineTerminator,
+}
+
+// Delimiters tried when sniffing a dialect, in order of preference.
+const DELIMITERS: &[u8] = b",\t;|";
+// Quote bytes tried when sniffing a dialect, in order of preference.
+const QUOTES: &[u8] = b"\"'";
+
+// Csv matches a comma-separated values file.
+pub(crate) struct CsvDetector;
+
+impl MimeDetector for CsvDetector {
+    fn detect(&self, content: &[u8], limit: usize) -> bool {
+        sv(content, b',', limit)
+    }
+}
+
+// Tsv matches a tab-separated values file.
+pub(crate) struct TsvDetector;
+
+impl MimeDetector for TsvDetector {
+    fn detect(&self, content: &[u8], limit: usize) -> bool {
+        sv(content, b'\t', limit)
+    }
+}
+
+fn sv(content: &[u8], delimiter: u8, limit: usize) -> bool {
+    let content = drop_last_line(content, limit);
+    let Some(parsed) = parse(content, delimiter, b'"') else {
+        return false;
+    };
+    is_consistent(&parsed.records)
+}
+
+/// Sniff the dialect of a delimiter separated values file.
+///
+/// Returns `None` if no delimiter splits the content into at least two
+/// records with the same number (more than one) of fields.
+pub(crate) fn sniff_dialect(content: &[u8], limit: usize) -> Option<CsvDialect> {
+    let content = drop_last_line(content, limit);
+    for &delimiter in DELIMITERS {
+        // Prefer the quote byte which actually opens fields; fall back to the
+        // first one that yields consistent records.
+        let mut fallback = None;
+        for &quote in QUOTES {
+            let Some(parsed) = parse(content, delimiter, quote) else {
+                continue;
+            };
+            if !is_consistent(&parsed.records) {
+                continue;
+            }
+            let dialect = CsvDialect {
+                delimiter,
+                quote: parsed.quoted.then_some(quote),
+                has_header: has_header(&parsed.records),
+                line_terminator: parsed.line_terminator,
+            };
+            if parsed.quoted {
+                return Some(dialect);
+            }
+            fallback.get_or_insert(dialect);
+        }
+        if fallback.is_some() {
+            return fallback;
+        }
+    }
+    None
+}
+
+fn is_consistent(records: &[Vec<Vec<u8>>]) -> bool {
+    if records.len() < 2 {
+        return false;
+    }
+    let fields = records[0].len();
+    fields > 1 && records.iter().all(|r| r.len() == fields)
+}
+
+// has_header votes on every column: a column whose values are all numeric, or
+// all of the same length, hints at a header when the first row breaks the pattern.
+fn has_header(records: &[Vec<Vec<u8>>]) -> bool {
+    let (header, rows) = (&records[0], &records[1..]);
+    let mut votes = 0i32;
+    for (col, name) in header.iter().enumerate() {
+        let values = rows.iter().map(|r| r[col].as_slice());
+        if values.clone().all(is_number) {
+            votes += if is_number(name) { -1 } else { 1 };
+            continue;
+        }
+        let mut lens = values.map(|v| v.len());
+        let Some(len) = lens.next() else {
+            continue;
+        };
+        if lens.all(|l| l == len) {
+            votes += if name.len() == len { -1 } else { 1 };
+        }
+    }
+    votes > 0
+}
+
+fn is_number(field: &[u8]) -> bool {
+    std::str::from_utf8(field)
+        .ok()
+        .is_some_and(|s| s.trim().parse::<f64>().is_ok())
+}
+
+struct Parsed {
+    records: Vec<Vec<Vec<u8>>>,
+    quoted: bool,
+    line_terminator: LineTerminator,
+}
+
+// parse splits content into records. Quotes are handled leniently: a quote in
+// an unquoted field is kept as is and a non-doubled quote in a quoted field
+// ends the quoting. Empty lines and lines starting with '#' are skipped.
+fn parse(content: &[u8], delimiter: u8, quote: u8) -> Option<Parsed> {
+    let mut content = content;
+    if let Some(enc) = from_boom(content) {
+        // Only UTF-8 can be split byte-wise.
+        if enc != "UTF-8" {
+            return None;
+        }
+        content = &content[3..];
+    }
+
+    let mut parsed = Parsed {
+        records: Vec::new(),
+        quoted: false,
+        line_terminator: LineTerminator::Lf,
+    };
+    let mut terminator = None;
+    let (mut record, mut field) = (Vec::new(), Vec::new());
+    let mut i = 0;
+    while i < content.len() {
+        let at_line_start = record.is_empty() && field.is_empty();
+        let b = content[i];
+        if at_line_start && b == b'#' {
+            while i < content.len() && content[i] != b'\n' && content[i] != b'\r' {
+                i += 1;
+            }
+            continue;
+        }
+        if at_line_start && (b == b'\n' || b == b'\r') {
+            i += 1;
+            continue;
+        }
+        if field.is_empty() && b == quote {
+            parsed.quoted = true;
+            i += 1;
+            while i < content.len() {
+                if content[i] == quote {
+                    if content.get(i + 1) == Some(&quote) {
+                        field.push(quote);
+                        i += 2;
+                        continue;
+                    }
+                    i += 1;
+                    break;
+                }
+                field.push(content[i]);
+                i += 1;
+            }
+            continue;
+        }
+        match b {
+            _ if b == delimiter => record.push(std::mem::take(&mut field)),
+            b'\n' | b'\r' => {
+                let t = match (b, content.get(i + 1)) {
+                    (b'\r', Some(b'\n')) => {
+                        i += 1;
+                        LineTerminator::CrLf
+                    }
+                    (b'\r', _) => LineTerminator::Cr,
+                    _ => LineTerminator::Lf,
+                };
+                terminator.get_or_insert(t);
+                record.push(std::mem::take(&mut field));
+                parsed.records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(b),
+        }
+        i += 1;
+    }
+    if !record.is_empty() || !field.is_empty() {
+        record.push(field);
+        parsed.records.push(record);
+    }
+    if let Some(t) = terminator {
+        parsed.line_terminator = t;
+    }
+
+    Some(parsed)
+}
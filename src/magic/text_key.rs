@@ -0,0 +1,141 @@
+use crate::mime::MimeDetector;
+
+use super::base::{first_line, trim_right_ws, BytesExt};
+
+// pem_label returns the label of the first PEM encapsulation boundary in content,
+// e.g. "CERTIFICATE" for "-----BEGIN CERTIFICATE-----", and the content after it.
+// Leading whitespace is ignored, and so are EC parameters blocks which OpenSSL
+// writes before EC private keys.
+fn pem_label(content: &[u8]) -> Option<(&[u8], &[u8])> {
+    let mut content = content.trim_ascii_start();
+    loop {
+        let line = trim_right_ws(first_line(content));
+        let label = line.strip_prefix(b"-----BEGIN ")?.strip_suffix(b"-----")?;
+        let rest = &content[line.len()..];
+        if label != b"EC PARAMETERS" {
+            return Some((label, rest));
+        }
+        let end = rest.index(b"-----END EC PARAMETERS-----")?;
+        content = rest[end + 27..].trim_ascii_start();
+    }
+}
+
+// Pem matches an ASCII armored file, starting with a "-----BEGIN <label>-----"
+// line. An empty list of labels matches any label.
+pub(crate) struct PemDetector {
+    pub labels: Vec<&'static [u8]>,
+}
+
+impl MimeDetector for PemDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        pem_label(content)
+            .is_some_and(|(label, _)| self.labels.is_empty() || self.labels.contains(&label))
+    }
+}
+
+// PemEncrypted matches a PEM file whose "Proc-Type" header marks the traditional
+// OpenSSL private key that follows as encrypted.
+pub(crate) struct PemEncryptedDetector;
+
+impl MimeDetector for PemEncryptedDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        pem_label(content).is_some_and(|(_, rest)| {
+            trim_right_ws(first_line(rest.trim_ascii_start())) == b"Proc-Type: 4,ENCRYPTED"
+        })
+    }
+}
+
+// OpenSshEncrypted matches an OpenSSH private key protected by a passphrase.
+// The key starts with "openssh-key-v1\0" followed by the cipher name, which is
+// "none" for unencrypted keys.
+pub(crate) struct OpenSshEncryptedDetector;
+
+impl MimeDetector for OpenSshEncryptedDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        // Base64 of "openssh-key-v1\0" and of the length of the cipher name.
+        const MAGIC: &[u8] = b"b3BlbnNzaC1rZXktdjEAAAAA";
+        // Base64 of the above followed by the "none" cipher name.
+        const UNENCRYPTED: &[u8] = b"b3BlbnNzaC1rZXktdjEAAAAABG5vbmU";
+        pem_label(content).is_some_and(|(_, rest)| {
+            let body = rest.trim_ascii_start();
+            body.starts_with(MAGIC) && !body.starts_with(UNENCRYPTED)
+        })
+    }
+}
+
+// Ppk matches a PuTTY private key file.
+// https://tartarus.org/~simon/putty-snapshots/htmldoc/AppendixC.html
+pub(crate) struct PpkDetector;
+
+impl MimeDetector for PpkDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        content.starts_with(b"PuTTY-User-Key-File-")
+            && trim_right_ws(first_line(content)).index(b": ").is_some()
+    }
+}
+
+// PpkEncrypted matches a PuTTY private key file protected by a passphrase.
+pub(crate) struct PpkEncryptedDetector;
+
+impl MimeDetector for PpkEncryptedDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        content
+            .split(|&b| b == b'\n')
+            .map(trim_right_ws)
+            .find_map(|l| l.strip_prefix(b"Encryption: "))
+            .is_some_and(|e| e != b"none")
+    }
+}
+
+// SshPublicKey matches an OpenSSH public key, "<algorithm> <base64 key> [comment]",
+// or an RFC 4716 public key file.
+pub(crate) struct SshPublicKeyDetector;
+
+impl MimeDetector for SshPublicKeyDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        const ALGORITHMS: &[&[u8]] = &[
+            b"ssh-rsa",
+            b"ssh-dss",
+            b"ssh-ed25519",
+            b"ssh-ed448",
+            b"ecdsa-sha2-nistp256",
+            b"ecdsa-sha2-nistp384",
+            b"ecdsa-sha2-nistp521",
+            b"sk-ssh-ed25519@openssh.com",
+            b"sk-ecdsa-sha2-nistp256@openssh.com",
+        ];
+        if content.starts_with(b"---- BEGIN SSH2 PUBLIC KEY ----") {
+            return true;
+        }
+        let mut fields = first_line(content).split(|&b| b == b' ');
+        let (Some(algorithm), Some(key)) = (fields.next(), fields.next()) else {
+            return false;
+        };
+        // The key blob starts with the length of the algorithm name, which is
+        // always encoded as "AAAA" in base64.
+        ALGORITHMS.contains(&algorithm) && key.starts_with(b"AAAA")
+    }
+}
+
+// The labels of PEM and PGP armors holding private key material.
+const PRIVATE_KEY_LABELS: &[&[u8]] = &[b"PRIVATE KEY", b"PGP PRIVATE KEY BLOCK"];
+
+/// Check whether the content holds private key material anywhere: a PEM or
+/// OpenSSH private key, a PGP private key block or a PuTTY private key.
+pub(crate) fn has_private_key(content: &[u8]) -> bool {
+    if content.index(b"PuTTY-User-Key-File-").is_some() {
+        return true;
+    }
+    let mut content = content;
+    while let Some(i) = content.index(b"-----BEGIN ") {
+        content = &content[i + 11..];
+        let line = first_line(content);
+        if let Some(end) = line.index(b"-----") {
+            let label = &line[..end];
+            if PRIVATE_KEY_LABELS.iter().any(|l| label.ends_with(l)) {
+                return true;
+            }
+        }
+    }
+    false
+}
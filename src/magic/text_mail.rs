@@ -0,0 +1,171 @@
+use crate::mime::MimeDetector;
+
+use super::base::{drop_last_line, first_line, trim_right_ws};
+
+// Header fields which only appear in mail messages.
+const MAIL_HEADERS: &[&[u8]] = &[
+    b"from",
+    b"to",
+    b"cc",
+    b"received",
+    b"return-path",
+    b"delivered-to",
+    b"message-id",
+    b"in-reply-to",
+];
+
+// Header fields commonly found in mail messages.
+const COMMON_HEADERS: &[&[u8]] = &[
+    b"subject",
+    b"date",
+    b"reply-to",
+    b"references",
+    b"mime-version",
+    b"content-type",
+    b"content-transfer-encoding",
+    b"dkim-signature",
+    b"authentication-results",
+    b"x-mailer",
+    b"user-agent",
+];
+
+// header_block parses the RFC 5322 header fields at the start of content, up to
+// the first empty line. Folded lines are appended to the value of their field.
+// Returns None if a line is not a header field, or is a YAML sequence item
+// rather than a folded line.
+fn header_block(content: &[u8]) -> Option<Vec<(Vec<u8>, Vec<u8>)>> {
+    let mut headers: Vec<(Vec<u8>, Vec<u8>)> = Vec::new();
+    for line in content.split(|&b| b == b'\n') {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        if line.is_empty() {
+            break;
+        }
+        if line[0] == b' ' || line[0] == b'\t' {
+            let item = line.trim_ascii_start();
+            if item == b"-" || item.starts_with(b"- ") {
+                return None;
+            }
+            let (_, value) = headers.last_mut()?;
+            value.extend_from_slice(line);
+            continue;
+        }
+        let colon = line.iter().position(|&b| b == b':')?;
+        let name = &line[..colon];
+        // Field names are printable US-ASCII characters, except colon.
+        if name.is_empty() || !name.iter().all(|b| (33..=126).contains(b)) {
+            return None;
+        }
+        headers.push((
+            name.to_ascii_lowercase(),
+            line[colon + 1..].trim_ascii().to_vec(),
+        ));
+    }
+    Some(headers)
+}
+
+fn header_value<'a>(headers: &'a [(Vec<u8>, Vec<u8>)], name: &[u8]) -> Option<&'a [u8]> {
+    headers
+        .iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, v)| v.as_slice())
+}
+
+// is_message checks content starts with the header block of a mail message.
+// Field names alone are shared with YAML and other "key: value" files, so an
+// address in From or To, or a Received or Message-ID field, is required too.
+fn is_message(content: &[u8], limit: usize) -> bool {
+    let Some(headers) = header_block(drop_last_line(content, limit)) else {
+        return false;
+    };
+    let count = |names: &[&[u8]]| {
+        headers
+            .iter()
+            .filter(|(n, _)| names.contains(&n.as_slice()))
+            .count()
+    };
+    let has_address = |name: &[u8]| {
+        header_value(&headers, name).is_some_and(|v| {
+            let angle = v.iter().position(|&b| b == b'<');
+            v.contains(&b'@') || angle.is_some_and(|i| v[i..].contains(&b'>'))
+        })
+    };
+    let evidence = has_address(b"from")
+        || has_address(b"to")
+        || header_value(&headers, b"received").is_some()
+        || header_value(&headers, b"message-id").is_some();
+    evidence && count(MAIL_HEADERS) + count(COMMON_HEADERS) >= 3
+}
+
+// Eml matches an Internet Message Format (RFC 5322) file.
+pub(crate) struct EmlDetector;
+
+impl MimeDetector for EmlDetector {
+    fn detect(&self, content: &[u8], limit: usize) -> bool {
+        is_message(content, limit)
+    }
+}
+
+// Mhtml matches a MIME HTML web archive: a message whose body is multipart/related.
+pub(crate) struct MhtmlDetector;
+
+impl MimeDetector for MhtmlDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        let Some(headers) = header_block(content) else {
+            return false;
+        };
+        header_value(&headers, b"content-type")
+            .is_some_and(|v| v.len() >= 17 && v[..17].eq_ignore_ascii_case(b"multipart/related"))
+    }
+}
+
+// Mbox matches a mailbox file: messages each preceded by a "From " line holding
+// the sender and the date the message was received.
+pub(crate) struct MboxDetector;
+
+impl MimeDetector for MboxDetector {
+    fn detect(&self, content: &[u8], limit: usize) -> bool {
+        if !content.starts_with(b"From ") {
+            return false;
+        }
+        // The date has a time of day, e.g. "From joe@example.com Mon Jan  1 10:20:30 2024".
+        let line = trim_right_ws(first_line(content));
+        let has_time = line.windows(5).any(|w| {
+            w[0].is_ascii_digit() && w[1].is_ascii_digit() && w[2] == b':' && w[3].is_ascii_digit()
+        });
+        has_time && is_message(content[line.len()..].trim_ascii_start(), limit)
+    }
+}
+
+// Multipart matches a MIME multipart body: a "--boundary" delimiter line
+// followed by the header fields of the first part.
+pub(crate) struct MultipartDetector;
+
+impl MimeDetector for MultipartDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        first_part_headers(content).is_some_and(|headers| {
+            !headers.is_empty() && headers.iter().all(|(n, _)| n.starts_with(b"content-"))
+        })
+    }
+}
+
+// FormData matches a multipart/form-data body, as sent by HTML forms.
+pub(crate) struct FormDataDetector;
+
+impl MimeDetector for FormDataDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        first_part_headers(content).is_some_and(|headers| {
+            header_value(&headers, b"content-disposition")
+                .is_some_and(|v| v.starts_with(b"form-data"))
+        })
+    }
+}
+
+fn first_part_headers(content: &[u8]) -> Option<Vec<(Vec<u8>, Vec<u8>)>> {
+    let line = first_line(content);
+    let boundary = trim_right_ws(line.strip_prefix(b"--")?);
+    // Boundaries are 1 to 70 characters long.
+    if boundary.is_empty() || boundary.len() > 70 || boundary.contains(&b' ') {
+        return None;
+    }
+    header_block(content.get(line.len() + 1..)?)
+}
@@ -0,0 +1,327 @@
+use crate::mime::MimeDetector;
+
+use super::base::{drop_last_line, trim_right_ws, BytesExt};
+
+// significant_lines returns the complete, non-empty lines of content that are
+// not comments. Comments start with any of the comment bytes, after indentation.
+fn significant_lines<'a>(
+    content: &'a [u8],
+    limit: usize,
+    comment: &'static [u8],
+) -> impl Iterator<Item = &'a [u8]> {
+    let content = content.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(content);
+    drop_last_line(content, limit)
+        .split(|&b| b == b'\n')
+        .map(trim_right_ws)
+        .filter(move |l| {
+            let t = l.trim_ascii_start();
+            !t.is_empty() && !comment.contains(&t[0])
+        })
+}
+
+fn indent(line: &[u8]) -> usize {
+    line.len() - line.trim_ascii_start().len()
+}
+
+// Yaml matches a YAML file: a "%YAML" directive, or lines made of "key: value"
+// mappings and "- item" sequences, indented with spaces.
+pub(crate) struct YamlDetector;
+
+impl MimeDetector for YamlDetector {
+    fn detect(&self, content: &[u8], limit: usize) -> bool {
+        if content.starts_with(b"%YAML ") {
+            return true;
+        }
+        let (mut documents, mut mappings) = (0, 0);
+        // Indentation of the line introducing a literal or folded block scalar.
+        let mut block: Option<usize> = None;
+        for line in significant_lines(content, limit, b"#") {
+            let indent = indent(line);
+            if let Some(b) = block {
+                if indent > b {
+                    continue;
+                }
+                block = None;
+            }
+            if line == b"---" || line.starts_with(b"--- ") || line == b"..." {
+                documents += 1;
+                continue;
+            }
+            // Tabs are not allowed for indentation in YAML.
+            if line[..indent].contains(&b'\t') {
+                return false;
+            }
+            let mut item = &line[indent..];
+            while let Some(rest) = item.strip_prefix(b"-") {
+                if !rest.is_empty() && rest[0] != b' ' {
+                    break;
+                }
+                item = rest.trim_ascii_start();
+            }
+            if item.is_empty() {
+                continue;
+            }
+            let Some(value) = yaml_mapping_value(item) else {
+                // Flow and plain scalars are accepted as sequence items only.
+                if item.len() != line.len() - indent {
+                    continue;
+                }
+                return false;
+            };
+            mappings += 1;
+            if value.starts_with(b"|") || value.starts_with(b">") {
+                block = Some(indent);
+            }
+        }
+
+        mappings >= 2 || (documents > 0 && mappings > 0)
+    }
+}
+
+// yaml_mapping_value returns the value of a "key: value" line, or None if the
+// line is not a mapping with a simple or quoted key.
+fn yaml_mapping_value(line: &[u8]) -> Option<&[u8]> {
+    let key_end = match line[0] {
+        q @ (b'"' | b'\'') => line[1..].iter().position(|&b| b == q)? + 2,
+        _ => line
+            .iter()
+            .position(|&b| !(b.is_ascii_alphanumeric() || b"_-./$@".contains(&b)))
+            .unwrap_or(line.len()),
+    };
+    if key_end == 0 || line.get(key_end) != Some(&b':') {
+        return None;
+    }
+    let value = &line[key_end + 1..];
+    if !value.is_empty() && value[0] != b' ' {
+        return None;
+    }
+    Some(value.trim_ascii_start())
+}
+
+// Toml matches a TOML file: "[table]" headers and "key = value" pairs whose
+// values are typed TOML values.
+pub(crate) struct TomlDetector;
+
+impl MimeDetector for TomlDetector {
+    fn detect(&self, content: &[u8], limit: usize) -> bool {
+        let (mut tables, mut pairs) = (0, 0);
+        // Open brackets of a multi-line array and the delimiter of a multi-line string.
+        let (mut depth, mut string): (i32, Option<&[u8]>) = (0, None);
+        for line in significant_lines(content, limit, b"#") {
+            if let Some(delim) = string {
+                if line.index(delim).is_some() {
+                    string = None;
+                }
+                continue;
+            }
+            if depth > 0 {
+                depth += bracket_depth(line);
+                continue;
+            }
+            let line = line.trim_ascii_start();
+            if line[0] == b'[' {
+                let header = strip_toml_comment(line);
+                let name = header
+                    .strip_prefix(b"[[")
+                    .and_then(|h| h.strip_suffix(b"]]"))
+                    .or_else(|| header.strip_prefix(b"[")?.strip_suffix(b"]"));
+                if !name.is_some_and(is_toml_key) {
+                    return false;
+                }
+                tables += 1;
+                continue;
+            }
+            let Some(eq) = line.iter().position(|&b| b == b'=') else {
+                return false;
+            };
+            if !is_toml_key(&line[..eq]) {
+                return false;
+            }
+            let value = line[eq + 1..].trim_ascii();
+            if value.starts_with(b"\"\"\"") || value.starts_with(b"'''") {
+                let delim = &value[..3];
+                if value[3..].index(delim).is_none() {
+                    string = Some(delim);
+                }
+            } else if value.starts_with(b"[") {
+                depth = bracket_depth(value);
+            } else if !is_toml_value(strip_toml_comment(value)) {
+                return false;
+            }
+            pairs += 1;
+        }
+
+        pairs > 0 && tables + pairs >= 2
+    }
+}
+
+// bracket_depth returns the number of opened minus closed square brackets in
+// line, ignoring those inside strings.
+fn bracket_depth(line: &[u8]) -> i32 {
+    let (mut depth, mut quote) = (0, None);
+    for &b in line {
+        match (quote, b) {
+            (Some(q), _) if b == q => quote = None,
+            (Some(_), _) => {}
+            (None, b'"' | b'\'') => quote = Some(b),
+            (None, b'[') => depth += 1,
+            (None, b']') => depth -= 1,
+            (None, b'#') => break,
+            _ => {}
+        }
+    }
+    depth
+}
+
+fn strip_toml_comment(value: &[u8]) -> &[u8] {
+    let mut quote = None;
+    for (i, &b) in value.iter().enumerate() {
+        match (quote, b) {
+            (Some(q), _) if b == q => quote = None,
+            (Some(_), _) => {}
+            (None, b'"' | b'\'') => quote = Some(b),
+            (None, b'#') => return value[..i].trim_ascii(),
+            _ => {}
+        }
+    }
+    value.trim_ascii()
+}
+
+// is_toml_key checks for bare, quoted or dotted keys.
+fn is_toml_key(key: &[u8]) -> bool {
+    let key = key.trim_ascii();
+    !key.is_empty()
+        && key.split(|&b| b == b'.').all(|part| {
+            let part = part.trim_ascii();
+            let quoted = part.len() >= 2
+                && (part[0] == b'"' || part[0] == b'\'')
+                && part[part.len() - 1] == part[0];
+            quoted
+                || (!part.is_empty()
+                    && part
+                        .iter()
+                        .all(|b| b.is_ascii_alphanumeric() || *b == b'_' || *b == b'-'))
+        })
+}
+
+// is_toml_value checks for single line strings, booleans, numbers, dates and
+// inline tables.
+fn is_toml_value(value: &[u8]) -> bool {
+    match value {
+        [] => false,
+        [q @ (b'"' | b'\''), .., last] => last == q && value.len() >= 2,
+        [b'{', .., b'}'] => true,
+        b"true" | b"false" | b"inf" | b"+inf" | b"-inf" | b"nan" | b"+nan" | b"-nan" => true,
+        [first, ..] => {
+            (first.is_ascii_digit() || *first == b'+' || *first == b'-')
+                && value
+                    .iter()
+                    .all(|b| b.is_ascii_hexdigit() || b"_+-.:xobTZ ".contains(b))
+        }
+    }
+}
+
+// Ini matches an INI file: "[section]" headers followed by "key=value" pairs.
+pub(crate) struct IniDetector;
+
+impl MimeDetector for IniDetector {
+    fn detect(&self, content: &[u8], limit: usize) -> bool {
+        let (mut sections, mut pairs) = (0, 0);
+        for line in significant_lines(content, limit, b";#") {
+            let line = line.trim_ascii_start();
+            if line[0] == b'[' {
+                if line.len() < 3 || line[line.len() - 1] != b']' {
+                    return false;
+                }
+                sections += 1;
+                continue;
+            }
+            match line.iter().position(|&b| b == b'=') {
+                Some(eq) if eq > 0 => pairs += 1,
+                _ => return false,
+            }
+        }
+
+        sections > 0 && pairs > 0
+    }
+}
+
+// Properties matches a Java .properties file: "key=value" or "key: value"
+// pairs, where keys have no whitespace and values may continue on the next
+// line after a backslash.
+pub(crate) struct PropertiesDetector;
+
+impl MimeDetector for PropertiesDetector {
+    fn detect(&self, content: &[u8], limit: usize) -> bool {
+        let (mut pairs, mut continued) = (0, false);
+        for line in significant_lines(content, limit, b"#!") {
+            let was_continued = continued;
+            continued = line.ends_with(b"\\");
+            if was_continued {
+                continue;
+            }
+            let line = line.trim_ascii_start();
+            let Some(sep) = line.iter().position(|&b| b == b'=' || b == b':') else {
+                return false;
+            };
+            if sep == 0 || line[..sep].trim_ascii_end().contains(&b' ') || line[0] == b'[' {
+                return false;
+            }
+            pairs += 1;
+        }
+
+        pairs >= 2
+    }
+}
+
+// Markdown matches a Markdown file. At least two kinds of Markdown constructs
+// are needed, one of them being a heading or a fenced code block, or three
+// kinds of constructs in total.
+pub(crate) struct MarkdownDetector;
+
+impl MimeDetector for MarkdownDetector {
+    fn detect(&self, content: &[u8], limit: usize) -> bool {
+        let content = content.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(content);
+        let (mut heading, mut fence, mut link, mut list, mut quote, mut table, mut emphasis) =
+            (false, false, false, false, false, false, false);
+        let mut previous: &[u8] = &[];
+        for line in drop_last_line(content, limit).split(|&b| b == b'\n') {
+            let line = trim_right_ws(line);
+            let t = line.trim_ascii_start();
+            let hashes = t.iter().take_while(|&&b| b == b'#').count();
+            if (1..=6).contains(&hashes) && t.get(hashes) == Some(&b' ') {
+                heading = true;
+            }
+            // Setext headings underline a line of text with "=" or "-".
+            if !previous.is_empty()
+                && t.len() >= 3
+                && (t.iter().all(|&b| b == b'=') || t.iter().all(|&b| b == b'-'))
+            {
+                heading = true;
+            }
+            if t.starts_with(b"```") || t.starts_with(b"~~~") {
+                fence = true;
+            }
+            if let Some(i) = t.index(b"](") {
+                link |= t[..i].contains(&b'[') && t[i..].contains(&b')');
+            }
+            let bullet = t.starts_with(b"- ") || t.starts_with(b"* ") || t.starts_with(b"+ ");
+            let digits = t.iter().take_while(|b| b.is_ascii_digit()).count();
+            list |= bullet || (digits > 0 && t[digits..].starts_with(b". "));
+            quote |= t.starts_with(b"> ");
+            table |= t.starts_with(b"|") && t.index(b"---").is_some();
+            emphasis |= t
+                .index(b"**")
+                .is_some_and(|i| t[i + 2..].index(b"**").is_some())
+                || t.index(b"`").is_some_and(|i| t[i + 1..].contains(&b'`'));
+            previous = t;
+        }
+
+        let others = [link, list, quote, table, emphasis]
+            .iter()
+            .filter(|&&f| f)
+            .count();
+        let strong = heading as usize + fence as usize;
+        (strong > 0 && strong + others >= 2) || others >= 3
+    }
+}
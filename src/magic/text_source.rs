@@ -0,0 +1,378 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use crate::mime::MimeDetector;
+
+use super::base::BytesExt;
+
+// The minimum confidence, as f32 bits, for source code to be reported. 0 disables
+// the classification.
+static SOURCE_THRESHOLD: AtomicU32 = AtomicU32::new(0);
+
+pub(crate) fn set_threshold(threshold: f32) {
+    SOURCE_THRESHOLD.store(threshold.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+}
+
+/// Language is a programming or configuration language recognized by the
+/// source code classifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    C,
+    Cpp,
+    Rust,
+    Go,
+    Java,
+    CSharp,
+    TypeScript,
+    Sql,
+    Shell,
+    Makefile,
+    Dockerfile,
+}
+
+impl Language {
+    /// The MIME type of the language.
+    pub fn mime(&self) -> &'static str {
+        match self {
+            Language::C => "text/x-c",
+            Language::Cpp => "text/x-c++",
+            Language::Rust => "text/x-rust",
+            Language::Go => "text/x-go",
+            Language::Java => "text/x-java",
+            Language::CSharp => "text/x-csharp",
+            Language::TypeScript => "application/typescript",
+            Language::Sql => "application/sql",
+            Language::Shell => "text/x-shellscript",
+            Language::Makefile => "text/x-makefile",
+            Language::Dockerfile => "text/x-dockerfile",
+        }
+    }
+
+    /// The extension of the language, empty for files identified by their name.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Language::C => ".c",
+            Language::Cpp => ".cpp",
+            Language::Rust => ".rs",
+            Language::Go => ".go",
+            Language::Java => ".java",
+            Language::CSharp => ".cs",
+            Language::TypeScript => ".ts",
+            Language::Sql => ".sql",
+            Language::Shell => ".sh",
+            Language::Makefile => "",
+            Language::Dockerfile => "",
+        }
+    }
+}
+
+// Pattern is a syntax feature searched for in the content.
+enum Pattern {
+    // Anywhere in the content.
+    Contains(&'static str),
+    // Anywhere in the content, ignoring ASCII case.
+    CiContains(&'static str),
+    // At the start of a line, after indentation.
+    LineStart(&'static str),
+    // At the very start of a line, without indentation.
+    Column0(&'static str),
+}
+
+struct Feature {
+    pattern: Pattern,
+    weight: f32,
+}
+
+const fn f(pattern: Pattern, weight: f32) -> Feature {
+    Feature { pattern, weight }
+}
+
+use Pattern::*;
+
+static FEATURES: &[(Language, &[Feature])] = &[
+    (
+        Language::C,
+        &[
+            f(LineStart("#include <stdio.h>"), 4.0),
+            f(LineStart("#include <stdlib.h>"), 4.0),
+            f(LineStart("#include <string.h>"), 3.0),
+            f(LineStart("#include "), 1.5),
+            f(LineStart("#define "), 1.5),
+            f(LineStart("#ifndef "), 1.0),
+            f(Contains("int main("), 2.0),
+            f(Contains("printf("), 1.0),
+            f(Contains("malloc("), 1.5),
+            f(Contains("sizeof("), 1.0),
+            f(Contains("typedef struct"), 2.0),
+            f(Contains("NULL"), 0.5),
+            f(Contains("->"), 0.3),
+        ],
+    ),
+    (
+        Language::Cpp,
+        &[
+            f(LineStart("#include <iostream>"), 5.0),
+            f(LineStart("#include <vector>"), 4.0),
+            f(LineStart("#include <string>"), 4.0),
+            f(LineStart("#include "), 1.0),
+            f(Contains("std::"), 2.5),
+            f(LineStart("namespace "), 1.5),
+            f(LineStart("using namespace "), 3.0),
+            f(Contains("template <"), 2.5),
+            f(Contains("template<"), 2.5),
+            f(LineStart("public:"), 2.5),
+            f(LineStart("private:"), 2.5),
+            f(Contains("cout <<"), 3.0),
+            f(Contains("nullptr"), 2.0),
+            f(Contains("::"), 0.3),
+        ],
+    ),
+    (
+        Language::Rust,
+        &[
+            f(LineStart("use std::"), 4.0),
+            f(LineStart("fn "), 2.0),
+            f(LineStart("pub fn "), 3.0),
+            f(LineStart("impl "), 2.5),
+            f(LineStart("impl<"), 3.0),
+            f(LineStart("#[derive("), 4.0),
+            f(LineStart("pub struct "), 2.0),
+            f(LineStart("mod "), 1.0),
+            f(Contains("let mut "), 3.0),
+            f(Contains("println!("), 3.0),
+            f(Contains("&mut "), 1.5),
+            f(Contains("&self"), 2.5),
+            f(Contains("Option<"), 1.0),
+            f(Contains("Result<"), 1.0),
+            f(Contains(".unwrap()"), 1.5),
+            f(Contains("pub(crate)"), 3.0),
+        ],
+    ),
+    (
+        Language::Go,
+        &[
+            f(Column0("package "), 2.0),
+            f(Column0("func "), 2.5),
+            f(Column0("import ("), 3.0),
+            f(Contains(" := "), 1.5),
+            f(Contains("fmt."), 2.0),
+            f(Contains("err != nil"), 3.0),
+            f(Contains("go func"), 3.0),
+            f(Contains("chan "), 1.0),
+            f(LineStart("defer "), 2.0),
+            f(Contains("interface {"), 1.5),
+            f(Contains(" struct {"), 1.5),
+        ],
+    ),
+    (
+        Language::Java,
+        &[
+            f(LineStart("import java."), 5.0),
+            f(LineStart("package "), 1.0),
+            f(Contains("public class "), 2.0),
+            f(Contains("System.out.print"), 4.0),
+            f(Contains("public static void main(String"), 5.0),
+            f(Contains("@Override"), 2.5),
+            f(Contains(" extends "), 1.0),
+            f(Contains(" implements "), 2.0),
+            f(Contains("private final "), 2.0),
+            f(Contains("String[]"), 1.5),
+        ],
+    ),
+    (
+        Language::CSharp,
+        &[
+            f(LineStart("using System"), 5.0),
+            f(LineStart("namespace "), 1.5),
+            f(Contains("public class "), 1.0),
+            f(Contains("Console.Write"), 4.0),
+            f(Contains("{ get; set; }"), 4.0),
+            f(Contains("async Task"), 3.0),
+            f(Contains("static void Main("), 4.0),
+            f(Contains("public override "), 2.0),
+            f(Contains("string[]"), 1.0),
+        ],
+    ),
+    (
+        Language::TypeScript,
+        &[
+            f(LineStart("import {"), 1.5),
+            f(LineStart("export "), 1.0),
+            f(LineStart("export interface "), 4.0),
+            f(LineStart("interface "), 2.0),
+            f(LineStart("type "), 1.0),
+            f(Contains(": string"), 2.0),
+            f(Contains(": number"), 2.0),
+            f(Contains(": boolean"), 2.0),
+            f(Contains(": void"), 2.0),
+            f(Contains("readonly "), 1.0),
+            f(Contains(" as const"), 2.0),
+            f(LineStart("const "), 0.5),
+            f(Contains(" => "), 0.5),
+        ],
+    ),
+    (
+        Language::Sql,
+        &[
+            f(CiContains("select "), 1.0),
+            f(CiContains(" from "), 1.0),
+            f(CiContains(" where "), 1.0),
+            f(CiContains("insert into "), 3.0),
+            f(CiContains("create table "), 4.0),
+            f(CiContains("alter table "), 3.0),
+            f(CiContains("delete from "), 3.0),
+            f(CiContains("primary key"), 3.0),
+            f(CiContains("varchar("), 3.0),
+            f(CiContains(" join "), 1.5),
+            f(CiContains("group by "), 2.0),
+            f(CiContains("order by "), 1.5),
+        ],
+    ),
+    (
+        Language::Shell,
+        &[
+            f(LineStart("echo "), 1.0),
+            f(LineStart("fi"), 2.0),
+            f(LineStart("esac"), 3.0),
+            f(LineStart("done"), 1.5),
+            f(LineStart("export "), 0.5),
+            f(LineStart("if [ "), 3.0),
+            f(LineStart("if [[ "), 3.0),
+            f(LineStart("elif "), 1.0),
+            f(Contains("; then"), 3.0),
+            f(Contains("; do"), 2.5),
+            f(Contains("$("), 1.0),
+            f(Contains("${"), 1.0),
+            f(Contains(" 2>&1"), 2.0),
+        ],
+    ),
+    (
+        Language::Makefile,
+        &[
+            f(Column0(".PHONY:"), 5.0),
+            f(Column0("all:"), 2.0),
+            f(Column0("clean:"), 2.0),
+            f(Column0("install:"), 1.5),
+            f(Column0("\t$("), 2.0),
+            f(Contains("$(CC)"), 3.0),
+            f(Contains("$(MAKE)"), 3.0),
+            f(Contains("$@"), 2.0),
+            f(Contains("$<"), 2.0),
+            f(Contains("$^"), 2.0),
+            f(Contains(" ?= "), 1.5),
+        ],
+    ),
+    (
+        Language::Dockerfile,
+        &[
+            f(Column0("FROM "), 3.0),
+            f(Column0("RUN "), 2.0),
+            f(Column0("COPY "), 2.0),
+            f(Column0("WORKDIR "), 3.0),
+            f(Column0("ENTRYPOINT "), 3.0),
+            f(Column0("CMD "), 2.0),
+            f(Column0("EXPOSE "), 3.0),
+            f(Column0("ENV "), 1.5),
+            f(Column0("ARG "), 1.5),
+            f(Column0("LABEL "), 1.5),
+        ],
+    ),
+];
+
+// A feature is counted at most this many times, so a long file repeating one
+// construct cannot outweigh the others.
+const MAX_FEATURE_COUNT: usize = 3;
+// The score at which the classifier is fully confident of the best language.
+const SATURATION_SCORE: f32 = 12.0;
+// The minimum score for the best language to be reported at all.
+const MIN_SCORE: f32 = 3.0;
+
+/// Classify the content as source code of one of the known languages.
+///
+/// The confidence, between 0 and 1, grows with the score of the best language
+/// and shrinks with the scores of the other languages.
+pub(crate) fn classify(content: &[u8]) -> Option<(Language, f32)> {
+    let lines: Vec<&[u8]> = content.split(|&b| b == b'\n').collect();
+    let lower = content.to_ascii_lowercase();
+
+    let mut scores = Vec::with_capacity(FEATURES.len());
+    for (language, features) in FEATURES {
+        let mut score = 0.0;
+        for feature in features.iter() {
+            let count = match feature.pattern {
+                Contains(p) => count(content, p.as_bytes()),
+                CiContains(p) => count(&lower, p.as_bytes()),
+                LineStart(p) => lines
+                    .iter()
+                    .filter(|l| l.trim_ascii_start().starts_with(p.as_bytes()))
+                    .take(MAX_FEATURE_COUNT)
+                    .count(),
+                Column0(p) => lines
+                    .iter()
+                    .filter(|l| l.starts_with(p.as_bytes()))
+                    .take(MAX_FEATURE_COUNT)
+                    .count(),
+            };
+            score += feature.weight * count as f32;
+        }
+        scores.push((*language, score));
+    }
+
+    let total: f32 = scores.iter().map(|(_, s)| s).sum();
+    let (language, best) = scores.into_iter().max_by(|a, b| a.1.total_cmp(&b.1))?;
+    if best < MIN_SCORE {
+        return None;
+    }
+    let confidence = best / total * (best / SATURATION_SCORE).min(1.0);
+    Some((language, confidence))
+}
+
+fn count(content: &[u8], pattern: &[u8]) -> usize {
+    let (mut n, mut content) = (0, content);
+    while n < MAX_FEATURE_COUNT {
+        let Some(i) = content.index(pattern) else {
+            break;
+        };
+        n += 1;
+        content = &content[i + pattern.len()..];
+    }
+    n
+}
+
+// classified returns the language of the content, when the classifier is enabled
+// and confident enough.
+fn classified(content: &[u8]) -> Option<Language> {
+    let threshold = f32::from_bits(SOURCE_THRESHOLD.load(Ordering::Relaxed));
+    if threshold == 0.0 {
+        return None;
+    }
+    classify(content)
+        .filter(|(_, c)| *c >= threshold)
+        .map(|(l, _)| l)
+}
+
+// Source matches a source code file of any language, when the classifier is
+// enabled and confident enough. The content is classified once, here, and the
+// child node of its language selected.
+pub(crate) struct SourceDetector;
+
+impl MimeDetector for SourceDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        classified(content).is_some()
+    }
+
+    fn select(&self, content: &[u8], _limit: usize) -> Option<Option<&'static str>> {
+        classified(content).map(|l| Some(l.mime()))
+    }
+}
+
+// Language matches a source code file of language. Under a SourceDetector, the
+// node of the language is selected without running this.
+pub(crate) struct LanguageDetector {
+    pub language: Language,
+}
+
+impl MimeDetector for LanguageDetector {
+    fn detect(&self, content: &[u8], _limit: usize) -> bool {
+        classified(content) == Some(self.language)
+    }
+}
@@ -0,0 +1,491 @@
+use std::borrow::Cow;
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::{
+    magic::{
+        archive::{tar_parse_octal, TarDetector, ZipDetector},
+        base::BytesExt,
+    },
+    mime::{detect, Mime, MimeDetector},
+};
+
+/// ArchiveLimits bounds the work done when walking the members of an archive,
+/// to guard against zip bombs and other malicious archives.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveLimits {
+    /// The maximum number of members reported, nested members included.
+    pub max_members: usize,
+    /// The maximum nesting level of archives walked inside archives.
+    pub max_depth: usize,
+    /// The maximum size of a member for it to be walked as a nested archive.
+    pub max_member_size: u64,
+    /// The maximum number of bytes decompressed over the whole walk.
+    pub max_total_size: u64,
+}
+
+impl Default for ArchiveLimits {
+    fn default() -> Self {
+        ArchiveLimits {
+            max_members: 10_000,
+            max_depth: 4,
+            max_member_size: 64 << 20,
+            max_total_size: 256 << 20,
+        }
+    }
+}
+
+/// ArchiveMember is a regular file stored in an archive.
+#[derive(Debug, Clone)]
+pub struct ArchiveMember {
+    /// The path of the member in the archive.
+    pub name: String,
+    /// The uncompressed size of the member, as recorded in the archive.
+    pub size: u64,
+    /// The MIME type of the member, `None` if its content could not be inspected:
+    /// it is encrypted, compressed with an unsupported method, overlaps another
+    /// member or is beyond the limits.
+    pub mime: Option<Mime>,
+    /// The members of the member, if it is an archive itself.
+    pub members: Vec<ArchiveMember>,
+}
+
+// Data is the content of a member as stored in the archive. It is cut short
+// when the archive is truncated.
+enum Data<'a> {
+    Stored(&'a [u8]),
+    #[cfg_attr(not(feature = "decompress"), allow(dead_code))]
+    Deflated(&'a [u8]),
+    Unsupported,
+}
+
+struct Entry<'a> {
+    name: String,
+    size: u64,
+    data: Data<'a>,
+}
+
+// walk returns the members of a zip, tar, ar or cpio archive, or None if the
+// content is not one of these archives. limit is the rate limit of the detection.
+pub(crate) fn walk(
+    content: &[u8],
+    limits: &ArchiveLimits,
+    limit: usize,
+) -> Option<Vec<ArchiveMember>> {
+    let mut walker = Walker {
+        limits,
+        limit,
+        members: 0,
+        decompressed: 0,
+    };
+    walker.walk(content, 0)
+}
+
+struct Walker<'l> {
+    limits: &'l ArchiveLimits,
+    limit: usize,
+    // The number of members reported so far.
+    members: usize,
+    // The number of bytes decompressed so far.
+    #[cfg_attr(not(feature = "decompress"), allow(dead_code))]
+    decompressed: u64,
+}
+
+impl Walker<'_> {
+    fn walk(&mut self, content: &[u8], depth: usize) -> Option<Vec<ArchiveMember>> {
+        let entries = entries(content)?;
+        let mut members = Vec::new();
+        for entry in entries {
+            if self.members >= self.limits.max_members {
+                break;
+            }
+            self.members += 1;
+
+            let mut member = ArchiveMember {
+                name: entry.name,
+                size: entry.size,
+                mime: None,
+                members: Vec::new(),
+            };
+            let Some(head) = self.read(&entry.data, self.limit) else {
+                members.push(member);
+                continue;
+            };
+            member.mime = Some(detect(&head));
+            if depth < self.limits.max_depth
+                && entry.size <= self.limits.max_member_size
+                && is_archive(&head)
+            {
+                let max = self.limits.max_member_size as usize;
+                if let Some(data) = self.read(&entry.data, max) {
+                    member.members = self.walk(&data, depth + 1).unwrap_or_default();
+                }
+            }
+            members.push(member);
+        }
+        Some(members)
+    }
+
+    // read returns the first max bytes of the content of a member, all of them
+    // if max is 0.
+    fn read<'a>(&mut self, data: &Data<'a>, max: usize) -> Option<Cow<'a, [u8]>> {
+        match data {
+            Data::Stored(d) if max > 0 && d.len() > max => Some(Cow::Borrowed(&d[..max])),
+            Data::Stored(d) => Some(Cow::Borrowed(d)),
+            #[cfg(feature = "decompress")]
+            Data::Deflated(d) => {
+                use std::io::Read;
+
+                let budget = self.limits.max_total_size.saturating_sub(self.decompressed);
+                let max = match max {
+                    0 => budget,
+                    _ => budget.min(max as u64),
+                };
+                if max == 0 {
+                    return None;
+                }
+                let mut buf = Vec::new();
+                let decoder = flate2::read::DeflateDecoder::new(*d);
+                // Truncated members end with an error, after the bytes inflated so far.
+                let _ = decoder.take(max).read_to_end(&mut buf);
+                self.decompressed += buf.len() as u64;
+                Some(Cow::Owned(buf))
+            }
+            #[cfg(not(feature = "decompress"))]
+            Data::Deflated(_) => None,
+            Data::Unsupported => None,
+        }
+    }
+}
+
+fn is_archive(content: &[u8]) -> bool {
+    ZipDetector {}.detect(content, 0)
+        || TarDetector {}.detect(content, 0)
+        || content.starts_with(b"!<arch>\n")
+        || is_cpio(content)
+}
+
+fn is_cpio(content: &[u8]) -> bool {
+    content.starts_with(b"070701")
+        || content.starts_with(b"070702")
+        || content.starts_with(b"070707")
+}
+
+fn entries(content: &[u8]) -> Option<Vec<Entry<'_>>> {
+    if (ZipDetector {}).detect(content, 0) {
+        Some(zip_entries(content))
+    } else if (TarDetector {}).detect(content, 0) {
+        Some(tar_entries(content))
+    } else if content.starts_with(b"!<arch>\n") {
+        Some(ar_entries(content))
+    } else if is_cpio(content) {
+        Some(cpio_entries(content))
+    } else {
+        None
+    }
+}
+
+// slice returns content[start..start + len], cut short at the end of content.
+fn slice(content: &[u8], start: usize, len: u64) -> &[u8] {
+    let start = start.min(content.len());
+    let end = start.saturating_add(len.min(usize::MAX as u64) as usize);
+    &content[start..end.min(content.len())]
+}
+
+// zip_entries reads the entries listed in the central directory of a zip archive,
+// falling back to the local file headers when the archive is truncated.
+// https://pkware.cachefly.net/webdocs/casestudies/APPNOTE.TXT
+fn zip_entries(content: &[u8]) -> Vec<Entry<'_>> {
+    // The end of central directory record is at most 22 + 65535 bytes from the end.
+    let tail = content.len().saturating_sub(22 + 0xFFFF);
+    let eocd = content[tail..]
+        .windows(4)
+        .rposition(|w| w == b"PK\x05\x06")
+        .map(|i| tail + i)
+        .filter(|i| content.len() >= i + 22);
+    let Some(eocd) = eocd else {
+        return zip_local_entries(content);
+    };
+
+    // (name, size, flags, method, compressed size, local header offset)
+    let mut records = Vec::new();
+    let mut p = LittleEndian::read_u32(&content[eocd + 16..eocd + 20]) as usize;
+    while let Some(h) = content.get(p..p.saturating_add(46)) {
+        if !h.starts_with(b"PK\x01\x02") {
+            break;
+        }
+        let (n, x, c) = (
+            LittleEndian::read_u16(&h[28..30]) as usize,
+            LittleEndian::read_u16(&h[30..32]) as usize,
+            LittleEndian::read_u16(&h[32..34]) as usize,
+        );
+        let name = slice(content, p + 46, n as u64);
+        records.push((
+            String::from_utf8_lossy(name).into_owned(),
+            LittleEndian::read_u32(&h[24..28]) as u64,
+            LittleEndian::read_u16(&h[8..10]),
+            LittleEndian::read_u16(&h[10..12]),
+            LittleEndian::read_u32(&h[20..24]) as u64,
+            LittleEndian::read_u32(&h[42..46]) as usize,
+        ));
+        p += 46 + n + x + c;
+    }
+
+    // Members sharing their data with others are the trademark of zip bombs.
+    records.sort_by_key(|r| r.5);
+    let mut end = 0;
+    let mut entries = Vec::new();
+    for (name, size, flags, method, compressed, offset) in records {
+        if name.ends_with('/') {
+            continue;
+        }
+        let data = match content.get(offset..offset + 30) {
+            Some(h) if h.starts_with(b"PK\x03\x04") && offset >= end => {
+                let start = offset
+                    + 30
+                    + LittleEndian::read_u16(&h[26..28]) as usize
+                    + LittleEndian::read_u16(&h[28..30]) as usize;
+                end = start.saturating_add(compressed as usize);
+                zip_data(slice(content, start, compressed), flags, method)
+            }
+            _ => Data::Unsupported,
+        };
+        entries.push(Entry { name, size, data });
+    }
+    entries
+}
+
+// zip_local_entries reads the entries of a zip archive from its local file
+// headers, up to the first one whose sizes are only known after its data.
+fn zip_local_entries(content: &[u8]) -> Vec<Entry<'_>> {
+    let mut entries = Vec::new();
+    let mut p: usize = 0;
+    while let Some(h) = content.get(p..p.saturating_add(30)) {
+        if !h.starts_with(b"PK\x03\x04") {
+            break;
+        }
+        let flags = LittleEndian::read_u16(&h[6..8]);
+        let compressed = LittleEndian::read_u32(&h[18..22]) as u64;
+        let size = LittleEndian::read_u32(&h[22..26]) as u64;
+        let n = LittleEndian::read_u16(&h[26..28]) as usize;
+        let x = LittleEndian::read_u16(&h[28..30]) as usize;
+        let name = String::from_utf8_lossy(slice(content, p + 30, n as u64)).into_owned();
+        let start = p + 30 + n + x;
+        // Bit 3: the sizes are in a data descriptor following the data.
+        let sized = flags & 0x08 == 0;
+        if !name.ends_with('/') {
+            let data = match sized {
+                true => zip_data(
+                    slice(content, start, compressed),
+                    flags,
+                    LittleEndian::read_u16(&h[8..10]),
+                ),
+                false => Data::Unsupported,
+            };
+            entries.push(Entry { name, size, data });
+        }
+        if !sized {
+            break;
+        }
+        p = start.saturating_add(compressed as usize);
+    }
+    entries
+}
+
+fn zip_data(data: &[u8], flags: u16, method: u16) -> Data<'_> {
+    // Bit 0: the member is encrypted.
+    match (flags & 0x01, method) {
+        (0, 0) => Data::Stored(data),
+        (0, 8) => Data::Deflated(data),
+        _ => Data::Unsupported,
+    }
+}
+
+// tar_entries reads the regular files of a tar archive, with their long names
+// from GNU and PAX extended headers.
+fn tar_entries(content: &[u8]) -> Vec<Entry<'_>> {
+    let mut entries = Vec::new();
+    let mut long_name = None;
+    let mut p: usize = 0;
+    while let Some(h) = content.get(p..p.saturating_add(512)) {
+        if h.iter().all(|&b| b == 0) || !(TarDetector {}).detect(h, 0) {
+            break;
+        }
+        let size = tar_size(&h[124..136]);
+        let data = slice(content, p + 512, size);
+        match h[156] {
+            b'L' => long_name = Some(String::from_utf8_lossy(trim_nul(data)).into_owned()),
+            b'x' => long_name = pax_path(data).or(long_name),
+            b'0' | b'\0' | b'7' => {
+                let name = long_name.take().unwrap_or_else(|| tar_name(h));
+                entries.push(Entry {
+                    name,
+                    size,
+                    data: Data::Stored(data),
+                });
+            }
+            _ => long_name = None,
+        }
+        p = (p + 512).saturating_add(size.div_ceil(512).saturating_mul(512) as usize);
+    }
+    entries
+}
+
+fn trim_nul(b: &[u8]) -> &[u8] {
+    let end = b.iter().position(|&c| c == 0).unwrap_or(b.len());
+    &b[..end]
+}
+
+fn tar_name(h: &[u8]) -> String {
+    let name = trim_nul(&h[..100]);
+    let prefix = trim_nul(&h[345..500]);
+    if h[257..].starts_with(b"ustar") && !prefix.is_empty() {
+        let mut full = prefix.to_vec();
+        full.push(b'/');
+        full.extend_from_slice(name);
+        return String::from_utf8_lossy(&full).into_owned();
+    }
+    String::from_utf8_lossy(name).into_owned()
+}
+
+// tar_size parses the size field of a tar header: octal digits, or a big-endian
+// number when the high bit of the first byte is set.
+fn tar_size(field: &[u8]) -> u64 {
+    if field[0] & 0x80 != 0 {
+        return field[1..]
+            .iter()
+            .fold(0u64, |n, &b| n.saturating_mul(256).saturating_add(b as u64));
+    }
+    tar_parse_octal(field).max(0) as u64
+}
+
+// pax_path returns the path record of PAX extended header records, each
+// written as "<length> <key>=<value>\n".
+fn pax_path(data: &[u8]) -> Option<String> {
+    data.split(|&b| b == b'\n').find_map(|record| {
+        let i = record.index(b" path=")?;
+        Some(String::from_utf8_lossy(&record[i + 6..]).into_owned())
+    })
+}
+
+// ar_entries reads the members of an ar archive, with GNU and BSD long names.
+fn ar_entries(content: &[u8]) -> Vec<Entry<'_>> {
+    let mut entries = Vec::new();
+    let mut names: &[u8] = &[];
+    let mut p: usize = 8;
+    while let Some(h) = content.get(p..p.saturating_add(60)) {
+        if &h[58..60] != b"`\n" {
+            break;
+        }
+        let Some(size) = std::str::from_utf8(&h[48..58])
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+        else {
+            break;
+        };
+        let mut data = slice(content, p + 60, size);
+        let name = h[..16].trim_ascii_end();
+        let name = match name {
+            // The symbol table and the GNU long names table.
+            b"/" | b"/SYM64/" | b"__.SYMDEF" | b"__.SYMDEF SORTED" => None,
+            b"//" => {
+                names = data;
+                None
+            }
+            _ if name.starts_with(b"#1/") => {
+                let len = std::str::from_utf8(&name[3..])
+                    .ok()
+                    .and_then(|s| s.parse::<usize>().ok())
+                    .unwrap_or(0)
+                    .min(data.len());
+                let long = trim_nul(&data[..len]);
+                data = &data[len..];
+                Some(long)
+            }
+            _ if name.len() > 1 && name[0] == b'/' => std::str::from_utf8(&name[1..])
+                .ok()
+                .and_then(|s| s.parse::<usize>().ok())
+                .and_then(|i| names.get(i..))
+                .map(|n| {
+                    let end = n.index(b"/\n").unwrap_or(n.len());
+                    &n[..end]
+                }),
+            _ => Some(name.strip_suffix(b"/").unwrap_or(name)),
+        };
+        if let Some(name) = name {
+            entries.push(Entry {
+                name: String::from_utf8_lossy(name).into_owned(),
+                size: data.len() as u64,
+                data: Data::Stored(data),
+            });
+        }
+        // Members are aligned on even offsets.
+        p = (p + 60).saturating_add((size + size % 2) as usize);
+    }
+    entries
+}
+
+// cpio_entries reads the regular files of a cpio archive in the portable ASCII
+// formats: "new" (070701 and 070702) and "old" (070707).
+fn cpio_entries(content: &[u8]) -> Vec<Entry<'_>> {
+    let mut entries = Vec::new();
+    let mut p: usize = 0;
+    while let Some(magic) = content.get(p..p.saturating_add(6)) {
+        let (mode, name, start, size) = if magic == b"070707" {
+            let Some(h) = content.get(p..p.saturating_add(76)) else {
+                break;
+            };
+            let (Some(mode), Some(namesize), Some(size)) = (
+                radix(&h[18..24], 8),
+                radix(&h[59..65], 8),
+                radix(&h[65..76], 8),
+            ) else {
+                break;
+            };
+            let name = slice(content, p + 76, namesize);
+            (mode, name, p + 76 + namesize as usize, size)
+        } else if magic == b"070701" || magic == b"070702" {
+            let Some(h) = content.get(p..p.saturating_add(110)) else {
+                break;
+            };
+            let (Some(mode), Some(size), Some(namesize)) = (
+                radix(&h[14..22], 16),
+                radix(&h[54..62], 16),
+                radix(&h[94..102], 16),
+            ) else {
+                break;
+            };
+            let name = slice(content, p + 110, namesize);
+            // The name and the data are padded to multiples of 4 bytes.
+            (
+                mode,
+                name,
+                (p + 110 + namesize as usize).next_multiple_of(4),
+                size,
+            )
+        } else {
+            break;
+        };
+        let name = trim_nul(name);
+        if name == b"TRAILER!!!" {
+            break;
+        }
+        let data = slice(content, start, size);
+        if mode & 0o170000 == 0o100000 {
+            entries.push(Entry {
+                name: String::from_utf8_lossy(name).into_owned(),
+                size,
+                data: Data::Stored(data),
+            });
+        }
+        p = start.saturating_add(size as usize);
+        if magic != b"070707" {
+            p = p.next_multiple_of(4);
+        }
+    }
+    entries
+}
+
+fn radix(field: &[u8], radix: u32) -> Option<u64> {
+    u64::from_str_radix(std::str::from_utf8(field).ok()?, radix).ok()
+}
@@ -4,7 +4,28 @@ use std::{
     vec,
 };
 
-use crate::{magic::base::EmptyDetector, tree::ROOT};
+use crate::{
+    magic::{
+        base::EmptyDetector,
+        image::read_image_info,
+        text_csv::sniff_dialect,
+        text_key::has_private_key,
+        text_source::{classify, set_threshold},
+    },
+    members::walk,
+    tree::{DISK_IMAGE, ROOT},
+};
+
+#[cfg(feature = "decompress")]
+use crate::decompress;
+
+pub use crate::members::{ArchiveLimits, ArchiveMember};
+
+pub use crate::magic::{
+    image::{ColorType, ImageInfo},
+    text_csv::{CsvDialect, LineTerminator},
+    text_source::Language,
+};
 
 const DEFAULT_LIMIT: usize = 3072;
 
@@ -20,8 +41,18 @@ pub fn set_rate_limit(limit: usize) {
 
 pub(crate) trait MimeDetector: Send + Sync {
     fn detect(&self, content: &[u8], limit: usize) -> bool;
+
+    // select is detect for detectors which also tell, by its MIME type, which
+    // of their node's children the content is, so it is inspected only once.
+    // None means no match, Some(None) that the children are tried in turn.
+    fn select(&self, content: &[u8], limit: usize) -> Option<Option<&'static str>> {
+        self.detect(content, limit).then_some(None)
+    }
 }
 
+// ParamsFn returns the optional MIME parameters, like a version, of the content.
+pub(crate) type ParamsFn = fn(&[u8]) -> Vec<(&'static str, String)>;
+
 /// MIME struct holds information about a file format: the string representation
 /// of the MIME type, the extension and the parent file format.
 pub struct Mime {
@@ -31,7 +62,10 @@ pub struct Mime {
     pub aliases: Vec<String>,
     /// The extension of the file format.
     pub extension: String,
+    /// The parameters of the MIME type, e.g. `("version", "4.0")` for a vCard.
+    pub params: Vec<(String, String)>,
     detector: Box<dyn MimeDetector>,
+    params_fn: Option<ParamsFn>,
     chilren: Vec<Mime>,
 }
 
@@ -44,7 +78,9 @@ impl Mime {
             mime,
             aliases: Vec::new(),
             extension,
+            params: Vec::new(),
             detector: Box::new(detector),
+            params_fn: None,
             chilren: Vec::new(),
         }
     }
@@ -59,14 +95,30 @@ impl Mime {
         self
     }
 
+    pub(crate) fn params(mut self, params: ParamsFn) -> Self {
+        self.params_fn = Some(params);
+        self
+    }
+
     fn match_mime(&self, content: &[u8], limit: usize) -> Mime {
         for c in &self.chilren {
-            if c.detector.detect(content, limit) {
-                return c.match_mime(content, limit);
-            }
+            let Some(selected) = c.detector.select(content, limit) else {
+                continue;
+            };
+            let node = selected
+                .and_then(|mime| c.chilren.iter().find(|g| g.mime == mime))
+                .unwrap_or(c);
+            return node.match_mime(content, limit);
         }
 
-        self.clone()
+        let mut m = self.clone();
+        if let Some(params) = self.params_fn {
+            m.params = params(content)
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect();
+        }
+        m
     }
 }
 
@@ -76,6 +128,7 @@ impl Debug for Mime {
             .field("mime", &self.mime)
             .field("aliases", &self.aliases)
             .field("extension", &self.extension)
+            .field("params", &self.params)
             .finish()
     }
 }
@@ -86,7 +139,9 @@ impl Clone for Mime {
             mime: self.mime.clone(),
             aliases: self.aliases.clone(),
             extension: self.extension.clone(),
+            params: self.params.clone(),
             detector: Box::new(EmptyDetector {}),
+            params_fn: self.params_fn,
             chilren: self.chilren.clone(),
         }
     }
@@ -105,6 +160,130 @@ pub fn detect(content: &[u8]) -> Mime {
     ROOT.match_mime(content, limit)
 }
 
+/// Sniff the dialect (delimiter, quote, header row and line terminator) of a
+/// CSV or TSV file.
+/// Only the first `limit` bytes of the content are used, as in [`detect`].
+/// Returns `None` if the content does not look like delimiter separated values.
+pub fn detect_csv_dialect(content: &[u8]) -> Option<CsvDialect> {
+    let limit = RATE_LIMIT.load(Ordering::Relaxed);
+    let mut content = content;
+    if limit > 0 && content.len() > limit {
+        content = &content[..limit];
+    }
+
+    sniff_dialect(content, limit)
+}
+
+/// Set the minimum confidence, between 0 and 1, for text files to be detected as
+/// source code of a [`Language`] by the heuristic classifier.
+/// The classifier is disabled by default, or when the threshold is set to 0.
+pub fn set_source_threshold(threshold: f32) {
+    set_threshold(threshold);
+}
+
+/// Classify the content as source code of a [`Language`] and return it along
+/// with the confidence, between 0 and 1, of the classifier.
+/// Unlike [`detect`], the result does not depend on [`set_source_threshold`].
+/// Only the first `limit` bytes of the content are used, as in [`detect`].
+pub fn detect_source_language(content: &[u8]) -> Option<(Language, f32)> {
+    let limit = RATE_LIMIT.load(Ordering::Relaxed);
+    let mut content = content;
+    if limit > 0 && content.len() > limit {
+        content = &content[..limit];
+    }
+
+    classify(content)
+}
+
+/// Check whether the content holds private key material: a PEM encoded private
+/// key (RSA, EC, DSA, PKCS#8 or OpenSSH, encrypted or not), an ASCII armored PGP
+/// private key block or a PuTTY private key.
+/// Unlike [`detect`], the key may appear anywhere in the content, e.g. after a
+/// certificate chain.
+/// Only the first `limit` bytes of the content are used, as in [`detect`].
+pub fn contains_private_key(content: &[u8]) -> bool {
+    let limit = RATE_LIMIT.load(Ordering::Relaxed);
+    let mut content = content;
+    if limit > 0 && content.len() > limit {
+        content = &content[..limit];
+    }
+
+    has_private_key(content)
+}
+
+/// Read the dimensions, bit depth, colour type, animation flag and EXIF
+/// orientation of a PNG, JPEG, GIF, WebP, BMP, AVIF or HEIC image from its header.
+/// Only the first `limit` bytes of the content are used, as in [`detect`], so
+/// properties stored further, like the frame header of a JPEG file after large
+/// metadata, may be missed.
+/// Returns `None` if the content is not one of these images or its header could
+/// not be read.
+pub fn image_info(content: &[u8]) -> Option<ImageInfo> {
+    let limit = RATE_LIMIT.load(Ordering::Relaxed);
+    let mut content = content;
+    if limit > 0 && content.len() > limit {
+        content = &content[..limit];
+    }
+
+    read_image_info(&ROOT.match_mime(content, limit), content)
+}
+
+/// Walk the members of a zip, tar, ar or cpio archive and detect their MIME
+/// types, recursively for members which are archives themselves.
+/// The whole archive is walked, within the limits, and the type of each member
+/// is detected from its first `limit` bytes, as in [`detect`].
+/// Deflated zip members are only inspected with the `decompress` feature.
+/// Returns `None` if the content is not a supported archive.
+pub fn archive_members(content: &[u8], limits: &ArchiveLimits) -> Option<Vec<ArchiveMember>> {
+    walk(content, limits, RATE_LIMIT.load(Ordering::Relaxed))
+}
+
+/// Detect the format of a disk or virtual machine image: ISO 9660 and UDF, QCOW,
+/// VMDK, VHD, VHDX, VDI, Apple disk images, SquashFS, LUKS, and raw images of
+/// ext2/3/4, FAT, exFAT or NTFS filesystems or with MBR or GPT partition tables.
+/// Unlike [`detect`], the whole content is used, as some of these formats are
+/// only recognized 32 KiB into the file or by a trailer at its end. [`detect`]
+/// recognizes them too, when the rate limit lets it see that far.
+/// Returns `None` if the content is not one of these images.
+pub fn detect_disk_image(content: &[u8]) -> Option<Mime> {
+    let m = DISK_IMAGE.match_mime(content, 0);
+    (m.mime != "application/octet-stream").then_some(m)
+}
+
+// The maximum number of compressed streams nested in each other, e.g. 2 for
+// a .tar.gz.gz file.
+#[cfg(feature = "decompress")]
+const MAX_NESTING: usize = 4;
+
+// The maximum length of a decompressed payload when there is no rate limit.
+#[cfg(feature = "decompress")]
+const MAX_PAYLOAD_LEN: usize = 1 << 20;
+
+#[cfg(feature = "decompress")]
+/// Detect the MIME type of the content and, if it is a gzip, bzip2, xz, zstd or
+/// lzip stream, the MIME type of the decompressed payload, recursively.
+/// The result starts with the outermost format, e.g. `application/gzip` followed
+/// by `application/x-tar` for a `.tar.gz` file.
+/// Only the first `limit` bytes of each payload are decompressed, as in
+/// [`detect`], but the compressed stream is read as far as needed to get them.
+/// Without a rate limit, payloads are cut to 1 MiB.
+pub fn detect_nested(content: &[u8]) -> Vec<Mime> {
+    let limit = RATE_LIMIT.load(Ordering::Relaxed);
+    let max = if limit > 0 { limit } else { MAX_PAYLOAD_LEN };
+
+    let mut result = vec![detect(content)];
+    let mut payload: Option<Vec<u8>> = None;
+    while result.len() <= MAX_NESTING {
+        let input = payload.as_deref().unwrap_or(content);
+        let Some(p) = decompress::payload(&result[result.len() - 1], input, max) else {
+            break;
+        };
+        result.push(ROOT.match_mime(&p, limit));
+        payload = Some(p);
+    }
+    result
+}
+
 #[cfg(feature = "async")]
 use tokio::io::{AsyncRead, AsyncReadExt};
 
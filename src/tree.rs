@@ -1,7 +1,11 @@
 use std::{sync::LazyLock, vec};
 
 use crate::{
-    magic::{archive::*, audio::*, base::*, binary::*, ftyp::QuickTimeDetector, image::*, text::*},
+    magic::{
+        archive::*, asn1::*, audio::*, base::*, binary::*, bytecode::*, disk::*, ebml::*, elf::*,
+        ftyp::*, image::*, macho::*, pe::*, riff::*, text::*, text_csv::*, text_key::*,
+        text_mail::*, text_markup::*, text_source::*,
+    },
     mime::Mime,
 };
 // use crate::base::text::HTML;
@@ -56,6 +60,142 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
         },
     );
 
+    // Aar matches an Android library archive.
+    let aar = Mime::new(
+        "application/x-android-library".to_string(),
+        ".aar".to_string(),
+        AarDetector {},
+    );
+
+    // Apk matches an Android application package.
+    let apk = Mime::new(
+        "application/vnd.android.package-archive".to_string(),
+        ".apk".to_string(),
+        ZipContainsDetector {
+            paths: vec![b"AndroidManifest.xml", b"classes.dex", b"resources.arsc"],
+        },
+    );
+
+    // Aab matches an Android App Bundle.
+    let aab = Mime::new(
+        "application/x-android-app-bundle".to_string(),
+        ".aab".to_string(),
+        ZipContainsDetector {
+            paths: vec![b"BundleConfig.pb", b"base/manifest/AndroidManifest.xml"],
+        },
+    );
+
+    // Ipa matches an iOS application archive.
+    let ipa = Mime::new(
+        "application/x-ios-app".to_string(),
+        ".ipa".to_string(),
+        ZipContainsDetector {
+            paths: vec![b"Payload/"],
+        },
+    );
+
+    // Xpi matches a Mozilla add-on.
+    let xpi = Mime::new(
+        "application/x-xpinstall".to_string(),
+        ".xpi".to_string(),
+        ZipContainsDetector {
+            paths: vec![b"META-INF/mozilla.rsa", b"install.rdf", b"chrome.manifest"],
+        },
+    );
+
+    // Vsix matches a Visual Studio or VS Code extension.
+    let vsix = Mime::new(
+        "application/vsix".to_string(),
+        ".vsix".to_string(),
+        ZipContainsDetector {
+            paths: vec![b"extension.vsixmanifest"],
+        },
+    );
+
+    // Nupkg matches a NuGet package.
+    let nupkg = Mime::new(
+        "application/x-nupkg".to_string(),
+        ".nupkg".to_string(),
+        NupkgDetector {},
+    );
+
+    // Wheel matches a Python wheel package.
+    let wheel = Mime::new(
+        "application/x-wheel+zip".to_string(),
+        ".whl".to_string(),
+        WheelDetector {},
+    );
+
+    // Kmz matches a zipped Keyhole Markup Language file.
+    let kmz = Mime::new(
+        "application/vnd.google-earth.kmz".to_string(),
+        ".kmz".to_string(),
+        KmzDetector {},
+    );
+
+    // Usdz matches a Universal Scene Description package.
+    let usdz = Mime::new(
+        "model/vnd.usdz+zip".to_string(),
+        ".usdz".to_string(),
+        UsdzDetector {},
+    );
+
+    // ThreemfPackage matches a 3D Manufacturing Format package.
+    let threemf_package = Mime::new(
+        "model/3mf".to_string(),
+        ".3mf".to_string(),
+        ZipContainsDetector {
+            paths: vec![b"3D/"],
+        },
+    )
+    .aliases(vec!["application/vnd.ms-3mfdocument"]);
+
+    // Sketch matches a Sketch design file.
+    let sketch = Mime::new(
+        "application/x-sketch".to_string(),
+        ".sketch".to_string(),
+        SketchDetector {},
+    );
+
+    // Keynote matches an Apple Keynote presentation.
+    let keynote = Mime::new(
+        "application/vnd.apple.keynote".to_string(),
+        ".key".to_string(),
+        IworkDetector { app: "keynote" },
+    );
+
+    // Numbers matches an Apple Numbers spreadsheet.
+    let numbers = Mime::new(
+        "application/vnd.apple.numbers".to_string(),
+        ".numbers".to_string(),
+        IworkDetector { app: "numbers" },
+    );
+
+    // Pages matches an Apple Pages document.
+    let pages = Mime::new(
+        "application/vnd.apple.pages".to_string(),
+        ".pages".to_string(),
+        IworkDetector { app: "pages" },
+    );
+
+    // Msix matches a Windows app package.
+    let msix = Mime::new(
+        "application/msix".to_string(),
+        ".msix".to_string(),
+        ZipContainsDetector {
+            paths: vec![b"AppxManifest.xml", b"AppxBlockMap.xml", b"AppxMetadata/"],
+        },
+    )
+    .aliases(vec!["application/vnd.ms-appx", "application/appx"]);
+
+    // Cbz matches a zipped comic book archive.
+    let cbz = Mime::new(
+        "application/vnd.comicbook+zip".to_string(),
+        ".cbz".to_string(),
+        CbzDetector {},
+    )
+    .aliases(vec!["application/x-cbz"]);
+
     // Jar matches a Java archive file.
     let jar = Mime::new(
         "application/jar".to_string(),
@@ -201,7 +341,35 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
         ZipDetector {},
     )
     .children(vec![
-        xlsx, docx, pptx, epub, jar, odt, ods, odp, odg, odf, odc, sxc,
+        xlsx,
+        docx,
+        pptx,
+        epub,
+        aar,
+        apk,
+        aab,
+        ipa,
+        xpi,
+        vsix,
+        nupkg,
+        wheel,
+        kmz,
+        usdz,
+        threemf_package,
+        sketch,
+        keynote,
+        numbers,
+        pages,
+        msix,
+        jar,
+        odt,
+        ods,
+        odp,
+        odg,
+        odf,
+        odc,
+        sxc,
+        cbz,
     ]);
 
     // Pdf matches a Portable Document Format file.
@@ -285,6 +453,28 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
     )
     .aliases(vec!["application/vnd.ms-word"]);
 
+    // Ost matches a Microsoft Outlook offline storage table file.
+    let ost = Mime::new(
+        "application/vnd.ms-outlook-ost".to_string(),
+        ".ost".to_string(),
+        OffsetDetector {
+            offset: 8,
+            sig: b"SO",
+        },
+    );
+
+    // Pst matches a Microsoft Outlook personal storage table file.
+    // https://learn.microsoft.com/en-us/openspecs/office_file_formats/ms-pst/
+    let pst = Mime::new(
+        "application/vnd.ms-outlook-pst".to_string(),
+        ".pst".to_string(),
+        PrefixDetector {
+            sigs: vec![b"!BDN"],
+        },
+    )
+    .aliases(vec!["application/vnd.ms-outlook"])
+    .children(vec![ost]);
+
     // Ole matches an Open Linking and Embedding file.
     //
     // https://en.wikipedia.org/wiki/Object_Linking_and_Embedding
@@ -316,26 +506,290 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
     )
     .aliases(vec!["image/x-psd", "application/photoshop"]);
 
+    // TimestampToken matches an RFC 3161 timestamp token.
+    let timestamp_token = Mime::new(
+        "application/timestamp-token".to_string(),
+        ".tst".to_string(),
+        TimestampTokenDetector {},
+    );
+
     // P7s matches an .p7s signature File (PEM, Base64).
     let p7s = Mime::new(
         "application/pkcs7-signature".to_string(),
         ".p7s".to_string(),
         P7sDetector {},
+    )
+    .children(vec![timestamp_token]);
+
+    // X509Certificate matches a DER encoded X.509 certificate.
+    let x509_certificate = Mime::new(
+        "application/pkix-cert".to_string(),
+        ".cer".to_string(),
+        X509CertificateDetector {},
+    )
+    .aliases(vec!["application/x-x509-ca-cert"]);
+
+    // X509Crl matches a DER encoded X.509 certificate revocation list.
+    let x509_crl = Mime::new(
+        "application/pkix-crl".to_string(),
+        ".crl".to_string(),
+        X509CrlDetector {},
+    );
+
+    // Pkcs8Der matches a DER encoded PKCS#8 private key.
+    let pkcs8_der = Mime::new(
+        "application/pkcs8".to_string(),
+        ".p8".to_string(),
+        Pkcs8Detector {},
+    );
+
+    // Pkcs8EncryptedDer matches a DER encoded PKCS#8 encrypted private key.
+    let pkcs8_encrypted_der = Mime::new(
+        "application/pkcs8-encrypted".to_string(),
+        ".p8".to_string(),
+        Pkcs8EncryptedDetector {},
+    );
+
+    // Pkcs12 matches a PKCS#12 (PFX) file.
+    let pkcs12 = Mime::new(
+        "application/x-pkcs12".to_string(),
+        ".p12".to_string(),
+        Pkcs12Detector {},
+    )
+    .aliases(vec!["application/pkcs12"]);
+
+    // TimestampQuery matches an RFC 3161 timestamp request.
+    let timestamp_query = Mime::new(
+        "application/timestamp-query".to_string(),
+        ".tsq".to_string(),
+        TimestampQueryDetector {},
+    );
+
+    // TimestampReply matches an RFC 3161 timestamp response.
+    let timestamp_reply = Mime::new(
+        "application/timestamp-reply".to_string(),
+        ".tsr".to_string(),
+        TimestampReplyDetector {},
+    );
+
+    // Certificate matches a PEM encoded X.509 certificate.
+    let certificate = Mime::new(
+        "application/x-x509-ca-cert".to_string(),
+        ".crt".to_string(),
+        PemDetector {
+            labels: vec![b"CERTIFICATE", b"X509 CERTIFICATE", b"TRUSTED CERTIFICATE"],
+        },
+    )
+    .aliases(vec![
+        "application/x-x509-user-cert",
+        "application/x-pem-certificate",
+    ]);
+
+    // Csr matches a PEM encoded PKCS#10 certificate signing request.
+    let csr = Mime::new(
+        "application/pkcs10".to_string(),
+        ".csr".to_string(),
+        PemDetector {
+            labels: vec![b"CERTIFICATE REQUEST", b"NEW CERTIFICATE REQUEST"],
+        },
+    );
+
+    // Crl matches a PEM encoded X.509 certificate revocation list.
+    let crl = Mime::new(
+        "application/pkix-crl".to_string(),
+        ".crl".to_string(),
+        PemDetector {
+            labels: vec![b"X509 CRL"],
+        },
+    );
+
+    // Pkcs8 matches a PEM encoded PKCS#8 private key.
+    let pkcs8 = Mime::new(
+        "application/pkcs8".to_string(),
+        ".p8".to_string(),
+        PemDetector {
+            labels: vec![b"PRIVATE KEY"],
+        },
+    );
+
+    // Pkcs8Encrypted matches a PEM encoded PKCS#8 encrypted private key.
+    let pkcs8_encrypted = Mime::new(
+        "application/pkcs8-encrypted".to_string(),
+        ".p8".to_string(),
+        PemDetector {
+            labels: vec![b"ENCRYPTED PRIVATE KEY"],
+        },
+    );
+
+    // RsaKeyEncrypted matches a traditional OpenSSL RSA private key protected by a passphrase.
+    let rsa_key_encrypted = Mime::new(
+        "application/x-rsa-private-key-encrypted".to_string(),
+        ".key".to_string(),
+        PemEncryptedDetector {},
+    );
+
+    // RsaKey matches a traditional OpenSSL (PKCS#1) RSA private key.
+    let rsa_key = Mime::new(
+        "application/x-rsa-private-key".to_string(),
+        ".key".to_string(),
+        PemDetector {
+            labels: vec![b"RSA PRIVATE KEY"],
+        },
+    )
+    .children(vec![rsa_key_encrypted]);
+
+    // EcKeyEncrypted matches a traditional OpenSSL EC private key protected by a passphrase.
+    let ec_key_encrypted = Mime::new(
+        "application/x-ec-private-key-encrypted".to_string(),
+        ".key".to_string(),
+        PemEncryptedDetector {},
+    );
+
+    // EcKey matches a traditional OpenSSL (SEC 1) EC private key.
+    let ec_key = Mime::new(
+        "application/x-ec-private-key".to_string(),
+        ".key".to_string(),
+        PemDetector {
+            labels: vec![b"EC PRIVATE KEY"],
+        },
+    )
+    .children(vec![ec_key_encrypted]);
+
+    // DsaKeyEncrypted matches a traditional OpenSSL DSA private key protected by a passphrase.
+    let dsa_key_encrypted = Mime::new(
+        "application/x-dsa-private-key-encrypted".to_string(),
+        ".key".to_string(),
+        PemEncryptedDetector {},
+    );
+
+    // DsaKey matches a traditional OpenSSL DSA private key.
+    let dsa_key = Mime::new(
+        "application/x-dsa-private-key".to_string(),
+        ".key".to_string(),
+        PemDetector {
+            labels: vec![b"DSA PRIVATE KEY"],
+        },
+    )
+    .children(vec![dsa_key_encrypted]);
+
+    // OpenSshKeyEncrypted matches an OpenSSH private key protected by a passphrase.
+    let openssh_key_encrypted = Mime::new(
+        "application/x-openssh-private-key-encrypted".to_string(),
+        "".to_string(),
+        OpenSshEncryptedDetector {},
+    );
+
+    // OpenSshKey matches an OpenSSH private key.
+    let openssh_key = Mime::new(
+        "application/x-openssh-private-key".to_string(),
+        "".to_string(),
+        PemDetector {
+            labels: vec![b"OPENSSH PRIVATE KEY"],
+        },
+    )
+    .children(vec![openssh_key_encrypted]);
+
+    // Pem matches a Privacy-Enhanced Mail file, holding any other PEM encoded data.
+    let pem = Mime::new(
+        "application/x-pem-file".to_string(),
+        ".pem".to_string(),
+        PemDetector { labels: vec![] },
+    )
+    .children(vec![
+        certificate,
+        csr,
+        crl,
+        pkcs8,
+        pkcs8_encrypted,
+        rsa_key,
+        ec_key,
+        dsa_key,
+        openssh_key,
+    ]);
+
+    // PgpMessage matches an ASCII armored OpenPGP message.
+    let pgp_message = Mime::new(
+        "application/pgp-encrypted".to_string(),
+        ".asc".to_string(),
+        PemDetector {
+            labels: vec![b"PGP MESSAGE"],
+        },
+    );
+
+    // PgpPublicKey matches an ASCII armored OpenPGP public key.
+    let pgp_public_key = Mime::new(
+        "application/pgp-keys".to_string(),
+        ".asc".to_string(),
+        PemDetector {
+            labels: vec![b"PGP PUBLIC KEY BLOCK"],
+        },
+    );
+
+    // PgpPrivateKey matches an ASCII armored OpenPGP private key.
+    let pgp_private_key = Mime::new(
+        "application/x-pgp-private-key".to_string(),
+        ".asc".to_string(),
+        PemDetector {
+            labels: vec![b"PGP PRIVATE KEY BLOCK"],
+        },
+    );
+
+    // PgpSignature matches an ASCII armored OpenPGP signature, or a cleartext
+    // signed message.
+    let pgp_signature = Mime::new(
+        "application/pgp-signature".to_string(),
+        ".asc".to_string(),
+        PemDetector {
+            labels: vec![b"PGP SIGNATURE", b"PGP SIGNED MESSAGE"],
+        },
+    );
+
+    // PpkEncrypted matches a PuTTY private key file protected by a passphrase.
+    let ppk_encrypted = Mime::new(
+        "application/x-putty-private-key-encrypted".to_string(),
+        ".ppk".to_string(),
+        PpkEncryptedDetector {},
+    );
+
+    // Ppk matches a PuTTY private key file.
+    let ppk = Mime::new(
+        "application/x-putty-private-key".to_string(),
+        ".ppk".to_string(),
+        PpkDetector {},
+    )
+    .children(vec![ppk_encrypted]);
+
+    // SshPublicKey matches an OpenSSH or RFC 4716 public key file.
+    let ssh_public_key = Mime::new(
+        "application/x-ssh-public-key".to_string(),
+        ".pub".to_string(),
+        SshPublicKeyDetector {},
     );
 
+    // Opus matches an Ogg file holding Opus audio.
+    let opus = Mime::new(
+        "audio/opus".to_string(),
+        ".opus".to_string(),
+        OggOpusDetector {},
+    )
+    .params(ogg_params);
+
     // OggAudio matches an audio ogg file.
     let ogg_audio = Mime::new(
         "audio/ogg".to_string(),
         ".ogg".to_string(),
         OggAudioDetector {},
-    );
+    )
+    .params(ogg_params)
+    .children(vec![opus]);
 
     // OggVideo matches a video ogg file.
     let ogg_video = Mime::new(
         "video/ogg".to_string(),
         ".ogv".to_string(),
         OggVideoDetector {},
-    );
+    )
+    .params(ogg_params);
 
     // Ogg matches an Ogg file.
     let ogg = Mime::new(
@@ -427,13 +881,119 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
         },
     );
 
+    // Rf64 matches a Waveform Audio File Format file with 64 bits sizes, in
+    // RF64 or BW64 container.
+    let rf64 = Mime::new(
+        "audio/x-rf64".to_string(),
+        ".wav".to_string(),
+        Rf64Detector {},
+    )
+    .params(wav_params);
+
+    // Wav matches a Waveform Audio File Format file.
+    let wav = Mime::new(
+        "audio/wav".to_string(),
+        ".wav".to_string(),
+        RiffFormDetector { form: b"WAVE" },
+    )
+    .aliases(vec!["audio/x-wav", "audio/vnd.wave", "audio/wave"])
+    .params(wav_params)
+    .children(vec![rf64]);
+
+    // Avi matches an Audio Video Interleaved file.
+    let avi = Mime::new(
+        "video/x-msvideo".to_string(),
+        ".avi".to_string(),
+        RiffFormDetector { form: b"AVI " },
+    )
+    .aliases(vec!["video/avi", "video/msvideo"]);
+
     // Webp matches a WebP file.
     let webp = Mime::new(
         "image/webp".to_string(),
         ".webp".to_string(),
-        WebpDetector {},
+        RiffFormDetector { form: b"WEBP" },
+    );
+
+    // Qcp matches a Qualcomm Pure Voice file.
+    let qcp = Mime::new(
+        "audio/qcelp".to_string(),
+        ".qcp".to_string(),
+        RiffFormDetector { form: b"QLCM" },
+    );
+
+    // Ani matches a Windows animated cursor file.
+    let ani = Mime::new(
+        "application/x-navi-animation".to_string(),
+        ".ani".to_string(),
+        RiffFormDetector { form: b"ACON" },
+    );
+
+    // Rmid matches a MIDI file wrapped in a RIFF container.
+    let rmid = Mime::new(
+        "audio/midi".to_string(),
+        ".rmi".to_string(),
+        RiffFormDetector { form: b"RMID" },
+    )
+    .aliases(vec!["audio/mid"]);
+
+    // Dls matches a Downloadable Sounds instrument collection.
+    // https://www.rfc-editor.org/rfc/rfc4613.html
+    let dls = Mime::new(
+        "audio/dls".to_string(),
+        ".dls".to_string(),
+        RiffFormDetector { form: b"DLS " },
+    );
+
+    // Cda matches a CD audio track shortcut, as listed by Windows.
+    let cda = Mime::new(
+        "application/x-cdf".to_string(),
+        ".cda".to_string(),
+        RiffFormDetector { form: b"CDDA" },
     );
 
+    // Riff matches a Resource Interchange File Format container. The form type
+    // in its header tells what its chunks hold.
+    let riff = Mime::new(
+        "application/x-riff".to_string(),
+        ".riff".to_string(),
+        RiffDetector {},
+    )
+    .children(vec![wav, avi, webp, qcp, ani, rmid, dls, cda]);
+
+    // DosExe matches an MS-DOS executable file.
+    let dos_exe = Mime::new(
+        "application/x-dosexec".to_string(),
+        ".exe".to_string(),
+        DosExeDetector {},
+    )
+    .aliases(vec!["application/x-msdos-program"]);
+
+    // Efi matches a UEFI application or driver.
+    let efi = Mime::new(
+        "application/efi".to_string(),
+        ".efi".to_string(),
+        EfiDetector {},
+    )
+    .params(pe_params);
+
+    // PeDriver matches a Windows driver file.
+    let pe_driver = Mime::new(
+        "application/vnd.microsoft.portable-executable".to_string(),
+        ".sys".to_string(),
+        PeDriverDetector {},
+    )
+    .params(pe_params);
+
+    // Dll matches a Windows dynamic-link library file.
+    let dll = Mime::new(
+        "application/vnd.microsoft.portable-executable".to_string(),
+        ".dll".to_string(),
+        DllDetector {},
+    )
+    .aliases(vec!["application/x-msdownload"])
+    .params(pe_params);
+
     // Exe matches a Windows/DOS executable file.
     let exe = Mime::new(
         "application/vnd.microsoft.portable-executable".to_string(),
@@ -441,35 +1001,59 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
         PrefixDetector {
             sigs: vec![&[0x4D, 0x5A]],
         },
-    );
+    )
+    .params(pe_params)
+    .children(vec![dos_exe, efi, pe_driver, dll]);
+
+    // KernelModule matches a Linux kernel module file.
+    let kernel_module = Mime::new(
+        "application/x-linux-kernel-module".to_string(),
+        ".ko".to_string(),
+        KernelModuleDetector {},
+    )
+    .params(elf_params);
 
     // ElfObj matches an object file.
     let elf_obj = Mime::new(
         "application/x-object".to_string(),
         "".to_string(),
         ElfObjDetector {},
-    );
+    )
+    .params(elf_params)
+    .children(vec![kernel_module]);
 
     // ElfExe matches an executable file.
     let elf_exe = Mime::new(
         "application/x-executable".to_string(),
         "".to_string(),
         ElfExeDetector {},
-    );
+    )
+    .params(elf_params);
 
     // ElfLib matches a shared library file.
     let elf_lib = Mime::new(
         "application/x-sharedlib".to_string(),
         ".so".to_string(),
         ElfLibDetector {},
-    );
+    )
+    .params(elf_params);
 
     // ElfDump matches a core dump file.
     let elf_dump = Mime::new(
         "application/x-coredump".to_string(),
         "".to_string(),
         ElfDumpDetector {},
-    );
+    )
+    .params(elf_params);
+
+    // AppImage matches an AppImage application bundle.
+    let appimage = Mime::new(
+        "application/vnd.appimage".to_string(),
+        ".AppImage".to_string(),
+        AppImageDetector {},
+    )
+    .aliases(vec!["application/x-iso9660-appimage"])
+    .params(elf_params);
 
     // Elf matches an Executable and Linkable Format file.
     let elf = Mime::new(
@@ -479,7 +1063,8 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
             sigs: vec![&[0x7F, 0x45, 0x4C, 0x46]],
         },
     )
-    .children(vec![elf_obj, elf_exe, elf_lib, elf_dump]);
+    .params(elf_params)
+    .children(vec![appimage, elf_obj, elf_exe, elf_lib, elf_dump]);
 
     // Deb matches a Debian package file.
     let deb = Mime::new(
@@ -619,13 +1204,6 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
     )
     .aliases(vec!["audio/amr-nb"]);
 
-    // Wav matches a Waveform Audio File Format file.
-    let wav = Mime::new("audio/wav".to_string(), ".wav".to_string(), WavDetector {}).aliases(vec![
-        "audio/x-wav",
-        "audio/vnd.wave",
-        "audio/wave",
-    ]);
-
     // Aiff matches Audio Interchange File Format file.
     let aiff = Mime::new(
         "audio/aiff".to_string(),
@@ -666,6 +1244,14 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
         },
     );
 
+    // Mp4Audio matches an MP4 file holding only audio tracks.
+    let mp4_audio = Mime::new(
+        "audio/mp4".to_string(),
+        ".mp4".to_string(),
+        Mp4AudioDetector {},
+    )
+    .aliases(vec!["audio/x-m4a", "audio/x-mp4a"]);
+
     // Mp4 matches an MP4 file.
     let mp4 = Mime::new(
         "video/mp4".to_string(),
@@ -678,15 +1264,24 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
                 b"F4P ",
             ],
         },
+    )
+    .children(vec![mp4_audio]);
+
+    // WebMAudio matches a WebM file holding only audio tracks.
+    let webm_audio = Mime::new(
+        "audio/webm".to_string(),
+        ".webm".to_string(),
+        MatroskaAudioDetector {},
     );
 
     // WebM matches a WebM file.
     let webm = Mime::new(
         "video/webm".to_string(),
         ".webm".to_string(),
-        WebMDetector {},
+        EbmlDocTypeDetector { doc_type: b"webm" },
     )
-    .aliases(vec!["audio/webm"]);
+    .aliases(vec!["audio/webm"])
+    .children(vec![webm_audio]);
 
     // ThreeGP matches a 3GPP file.
     let three_gp = Mime::new(
@@ -713,14 +1308,6 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
     )
     .aliases(vec!["video/3g2", "audio/3gpp2"]);
 
-    // Avi matches an Audio Video Interleaved file.
-    let avi = Mime::new(
-        "video/x-msvideo".to_string(),
-        ".avi".to_string(),
-        AviDetector {},
-    )
-    .aliases(vec!["video/avi", "video/msvideo"]);
-
     // Flv matches a Flash video file.
     let flv = Mime::new(
         "video/x-flv".to_string(),
@@ -730,12 +1317,29 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
         },
     );
 
+    // Mka matches a Matroska file holding only audio tracks.
+    let mka = Mime::new(
+        "audio/x-matroska".to_string(),
+        ".mka".to_string(),
+        MatroskaAudioDetector {},
+    );
+
+    // Mk3d matches a Matroska file with stereoscopic video.
+    let mk3d = Mime::new(
+        "video/x-matroska-3d".to_string(),
+        ".mk3d".to_string(),
+        Matroska3dDetector {},
+    );
+
     // Mkv matches a mkv file.
     let mkv = Mime::new(
         "video/x-matroska".to_string(),
         ".mkv".to_string(),
-        MkvDetector {},
-    );
+        EbmlDocTypeDetector {
+            doc_type: b"matroska",
+        },
+    )
+    .children(vec![mka, mk3d]);
 
     // Asf matches an Advanced Systems Format file.
     let asf = Mime::new(
@@ -918,6 +1522,95 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
         },
     );
 
+    // Pyc matches a compiled Python bytecode file.
+    let pyc = Mime::new(
+        "application/x-python-code".to_string(),
+        ".pyc".to_string(),
+        PycDetector {},
+    )
+    .aliases(vec!["application/x-python-bytecode"])
+    .params(pyc_params);
+
+    // LuaBytecode matches a precompiled Lua file.
+    let lua_bytecode = Mime::new(
+        "application/x-lua-bytecode".to_string(),
+        ".luac".to_string(),
+        LuaBytecodeDetector {},
+    )
+    .params(lua_bytecode_params);
+
+    // Dex matches an Android Dalvik executable file.
+    let dex = Mime::new(
+        "application/vnd.android.dex".to_string(),
+        ".dex".to_string(),
+        DexDetector { magic: b"dex\n" },
+    )
+    .aliases(vec!["application/x-dex"])
+    .params(dex_params);
+
+    // Odex matches an optimized Android Dalvik executable file.
+    let odex = Mime::new(
+        "application/x-android-odex".to_string(),
+        ".odex".to_string(),
+        DexDetector { magic: b"dey\n" },
+    )
+    .params(dex_params);
+
+    // Vdex matches an Android verified Dalvik executables file.
+    let vdex = Mime::new(
+        "application/x-android-vdex".to_string(),
+        ".vdex".to_string(),
+        DexDetector { magic: b"vdex" },
+    )
+    .params(dex_params);
+
+    // Beam matches a compiled Erlang module.
+    let beam = Mime::new(
+        "application/x-erlang-binary".to_string(),
+        ".beam".to_string(),
+        BeamDetector {},
+    );
+
+    // OcamlInterface matches a compiled OCaml interface file.
+    let ocaml_interface = Mime::new(
+        "application/x-ocaml-interface".to_string(),
+        ".cmi".to_string(),
+        OcamlDetector { kinds: b"I" },
+    )
+    .params(ocaml_params);
+
+    // OcamlLibrary matches an OCaml bytecode library file.
+    let ocaml_library = Mime::new(
+        "application/x-ocaml-bytecode".to_string(),
+        ".cma".to_string(),
+        OcamlDetector { kinds: b"A" },
+    )
+    .params(ocaml_params);
+
+    // OcamlObject matches an OCaml bytecode object file.
+    let ocaml_object = Mime::new(
+        "application/x-ocaml-bytecode".to_string(),
+        ".cmo".to_string(),
+        OcamlDetector { kinds: b"O" },
+    )
+    .params(ocaml_params);
+
+    // OcamlExe matches an OCaml bytecode executable file, run by ocamlrun.
+    let ocaml_exe = Mime::new(
+        "application/x-ocaml-bytecode".to_string(),
+        ".byte".to_string(),
+        SheBangDetector {
+            interpreters: vec!["ocamlrun"],
+        },
+    );
+
+    // JavaSerialized matches a Java serialized object stream.
+    let java_serialized = Mime::new(
+        "application/x-java-serialized-object".to_string(),
+        ".ser".to_string(),
+        JavaSerializedDetector {},
+    );
+
     // Shp matches a shape format file.
     let shp = Mime::new(
         "application/vnd.shp".to_string(),
@@ -1043,19 +1736,79 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
         },
     );
 
-    // MachO matches Mach-O binaries format.
+    // MachOObject matches a Mach-O object file.
+    let macho_object = Mime::new(
+        "application/x-mach-o-object".to_string(),
+        ".o".to_string(),
+        MachOFileTypeDetector { file_type: 1 },
+    )
+    .params(macho_params);
+
+    // MachOExecutable matches a Mach-O executable file.
+    let macho_executable = Mime::new(
+        "application/x-mach-o-executable".to_string(),
+        "".to_string(),
+        MachOFileTypeDetector { file_type: 2 },
+    )
+    .params(macho_params);
+
+    // MachOCore matches a Mach-O core dump file.
+    let macho_core = Mime::new(
+        "application/x-mach-o-core".to_string(),
+        "".to_string(),
+        MachOFileTypeDetector { file_type: 4 },
+    )
+    .params(macho_params);
+
+    // MachODylib matches a Mach-O dynamic library file.
+    let macho_dylib = Mime::new(
+        "application/x-mach-o-dylib".to_string(),
+        ".dylib".to_string(),
+        MachOFileTypeDetector { file_type: 6 },
+    )
+    .params(macho_params);
+
+    // MachOBundle matches a Mach-O loadable bundle file.
+    let macho_bundle = Mime::new(
+        "application/x-mach-o-bundle".to_string(),
+        ".bundle".to_string(),
+        MachOFileTypeDetector { file_type: 8 },
+    )
+    .params(macho_params);
+
+    // MachODsym matches a Mach-O debug symbols file.
+    let macho_dsym = Mime::new(
+        "application/x-mach-o-dsym".to_string(),
+        "".to_string(),
+        MachOFileTypeDetector { file_type: 10 },
+    )
+    .params(macho_params);
+
+    // MachOKext matches a Mach-O kernel extension file.
+    let macho_kext = Mime::new(
+        "application/x-mach-o-kext-bundle".to_string(),
+        ".kext".to_string(),
+        MachOFileTypeDetector { file_type: 11 },
+    )
+    .params(macho_params);
+
+    // MachO matches Mach-O binaries format, single architecture or universal.
     let macho = Mime::new(
         "application/x-mach-binary".to_string(),
         ".macho".to_string(),
         MachODetector {},
-    );
-
-    // Qcp matches a Qualcomm Pure Voice file.
-    let qcp = Mime::new(
-        "audio/qcelp".to_string(),
-        ".qcp".to_string(),
-        QcpDetector {},
-    );
+    )
+    .aliases(vec!["application/x-mach-o"])
+    .params(macho_params)
+    .children(vec![
+        macho_object,
+        macho_executable,
+        macho_core,
+        macho_dylib,
+        macho_bundle,
+        macho_dsym,
+        macho_kext,
+    ]);
 
     // Icns matches an ICNS (Apple Icon Image format) file.
     let icns = Mime::new(
@@ -1066,12 +1819,22 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
         },
     );
 
+    // Cr3 matches a Canon Raw 3 image file.
+    // https://github.com/lclevy/canon_cr3
+    let cr3 = Mime::new(
+        "image/x-canon-cr3".to_string(),
+        ".cr3".to_string(),
+        FtypBrandsDetector {
+            brands: vec![b"crx "],
+        },
+    );
+
     // Heic matches a High Efficiency Image Coding (HEIC) file.
     let heic = Mime::new(
         "image/heic".to_string(),
         ".heic".to_string(),
-        FtypDetector {
-            sigs: vec![b"heic", b"heix"],
+        FtypBrandsDetector {
+            brands: vec![b"heic", b"heix"],
         },
     );
 
@@ -1079,8 +1842,8 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
     let heic_sequence = Mime::new(
         "image/heic-sequence".to_string(),
         ".heic".to_string(),
-        FtypDetector {
-            sigs: vec![b"hevc", b"hevx"],
+        FtypBrandsDetector {
+            brands: vec![b"hevc", b"hevx"],
         },
     );
 
@@ -1088,8 +1851,8 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
     let heif = Mime::new(
         "image/heif".to_string(),
         ".heif".to_string(),
-        FtypDetector {
-            sigs: vec![b"mif1", b"heim", b"heis", b"avic"],
+        FtypBrandsDetector {
+            brands: vec![b"mif1", b"heim", b"heis", b"avic"],
         },
     );
 
@@ -1097,8 +1860,8 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
     let heif_sequence = Mime::new(
         "image/heif-sequence".to_string(),
         ".heif".to_string(),
-        FtypDetector {
-            sigs: vec![b"msf1", b"hevm", b"hevs", b"avcs"],
+        FtypBrandsDetector {
+            brands: vec![b"msf1", b"hevm", b"hevs", b"avcs"],
         },
     );
 
@@ -1263,16 +2026,28 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
         },
     );
 
-    // AVIF matches an AV1 Image File Format still or animated.
-    // Wikipedia page seems outdated listing image/avif-sequence for animations.
+    // AvifSequence matches an animated AV1 Image File Format file. The unregistered
+    // image/avif-sequence type parallels image/heic-sequence and image/heif-sequence,
+    // so animations are told apart the same way for all HEIF based formats.
+    // image/avif, registered for both stills and animations, is kept as an alias.
     // https://github.com/AOMediaCodec/av1-avif/issues/59
+    let avif_sequence = Mime::new(
+        "image/avif-sequence".to_string(),
+        ".avif".to_string(),
+        AvifSequenceDetector {},
+    )
+    .aliases(vec!["image/avif"]);
+
+    // AVIF matches an AV1 Image File Format file. Animations are reported by its
+    // avif_sequence child.
     let avif = Mime::new(
         "image/avif".to_string(),
         ".avif".to_string(),
-        FtypDetector {
-            sigs: vec![b"avif", b"avis"],
+        FtypBrandsDetector {
+            brands: vec![b"avif", b"avis"],
         },
-    );
+    )
+    .children(vec![avif_sequence]);
 
     // InstallShieldCab matches an InstallShield Cabinet archive file.
     let installshield_cab = Mime::new(
@@ -1341,6 +2116,192 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
     )
     .aliases(vec!["application/rtf"]);
 
+    // Rss matches a Rich Site Summary file.
+    let rss = Mime::new(
+        "application/rss+xml".to_string(),
+        ".rss".to_string(),
+        XmlDetector {
+            sigs: vec![XmlSig::new("rss", "")],
+        },
+    )
+    .aliases(vec!["text/rss"]);
+
+    // Atom matches an Atom Syndication Format file.
+    let atom = Mime::new(
+        "application/atom+xml".to_string(),
+        ".atom".to_string(),
+        XmlDetector {
+            sigs: vec![XmlSig::new(
+                "feed",
+                r#"xmlns="http://www.w3.org/2005/Atom""#,
+            )],
+        },
+    );
+
+    // X3d matches an Extensible 3D Graphics file.
+    let x3d = Mime::new(
+        "model/x3d+xml".to_string(),
+        ".x3d".to_string(),
+        XmlDetector {
+            sigs: vec![XmlSig::new(
+                "X3D",
+                r#"xmlns:xsd="http://www.w3.org/2001/XMLSchema-instance""#,
+            )],
+        },
+    );
+
+    // Kml matches a Keyhole Markup Language file.
+    let kml = Mime::new(
+        "application/vnd.google-earth.kml+xml".to_string(),
+        ".kml".to_string(),
+        XmlDetector {
+            sigs: vec![
+                XmlSig::new("kml", r#"xmlns="http://www.opengis.net/kml/2.2""#),
+                XmlSig::new("kml", r#"xmlns="http://earth.google.com/kml/2.0""#),
+                XmlSig::new("kml", r#"xmlns="http://earth.google.com/kml/2.1""#),
+                XmlSig::new("kml", r#"xmlns="http://earth.google.com/kml/2.2""#),
+            ],
+        },
+    );
+
+    // Xliff matches a XML Localization Interchange File Format file.
+    let xliff = Mime::new(
+        "application/x-xliff+xml".to_string(),
+        ".xlf".to_string(),
+        XmlDetector {
+            sigs: vec![
+                XmlSig::new("xliff", r#"xmlns="urn:oasis:names:tc:xliff:document:1.2""#),
+                XmlSig::new("xliff", r#"xmlns="urn:oasis:names:tc:xliff:document:2.0""#),
+            ],
+        },
+    );
+
+    // Collada matches a COLLAborative Design Activity file.
+    let collada = Mime::new(
+        "model/vnd.collada+xml".to_string(),
+        ".dae".to_string(),
+        XmlDetector {
+            sigs: vec![XmlSig::new(
+                "COLLADA",
+                r#"xmlns="http://www.collada.org/2005/11/COLLADASchema""#,
+            )],
+        },
+    );
+
+    // Gml matches a Geography Markup Language file.
+    let gml = Mime::new(
+        "application/gml+xml".to_string(),
+        ".gml".to_string(),
+        XmlDetector {
+            sigs: vec![
+                XmlSig::new("", r#"xmlns:gml="http://www.opengis.net/gml""#),
+                XmlSig::new("", r#"xmlns:gml="http://www.opengis.net/gml/3.2""#),
+                XmlSig::new("", r#"xmlns:gml="http://www.opengis.net/gml/3.3/exr""#),
+            ],
+        },
+    );
+
+    // Gpx matches a GPS Exchange Format file.
+    let gpx = Mime::new(
+        "application/gpx+xml".to_string(),
+        ".gpx".to_string(),
+        XmlDetector {
+            sigs: vec![
+                XmlSig::new("gpx", r#"xmlns="http://www.topografix.com/GPX/1/1""#),
+                XmlSig::new("gpx", r#"xmlns="http://www.topografix.com/GPX/1/0""#),
+            ],
+        },
+    );
+
+    // Tcx matches a Training Center XML file.
+    let tcx = Mime::new(
+        "application/vnd.garmin.tcx+xml".to_string(),
+        ".tcx".to_string(),
+        XmlDetector {
+            sigs: vec![XmlSig::new(
+                "TrainingCenterDatabase",
+                r#"xmlns="http://www.garmin.com/xmlschemas/TrainingCenterDatabase/v2""#,
+            )],
+        },
+    );
+
+    // Amf matches an Additive Manufacturing XML file.
+    let amf = Mime::new(
+        "application/x-amf".to_string(),
+        ".amf".to_string(),
+        XmlDetector {
+            sigs: vec![XmlSig::new("amf", "")],
+        },
+    );
+
+    // Threemf matches a 3D Manufacturing Format model file.
+    let threemf = Mime::new(
+        "application/vnd.ms-package.3dmanufacturing-3dmodel+xml".to_string(),
+        ".3mf".to_string(),
+        XmlDetector {
+            sigs: vec![XmlSig::new(
+                "model",
+                r#"xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02""#,
+            )],
+        },
+    );
+
+    // Xfdf matches a XML Forms Data Format file.
+    let xfdf = Mime::new(
+        "application/vnd.adobe.xfdf".to_string(),
+        ".xfdf".to_string(),
+        XmlDetector {
+            sigs: vec![XmlSig::new("xfdf", r#"xmlns="http://ns.adobe.com/xfdf/""#)],
+        },
+    );
+
+    // Usf matches a Universal Subtitle Format file.
+    let usf = Mime::new(
+        "application/x-usf".to_string(),
+        ".usf".to_string(),
+        XmlDetector {
+            sigs: vec![XmlSig::new("USFSubtitles", "")],
+        },
+    );
+
+    // XCal matches a xCal (RFC 6321) file, the XML representation of an iCalendar.
+    let xcal = Mime::new(
+        "application/calendar+xml".to_string(),
+        ".xcs".to_string(),
+        XmlDetector {
+            sigs: vec![XmlSig::new(
+                "icalendar",
+                r#"xmlns="urn:ietf:params:xml:ns:icalendar-2.0""#,
+            )],
+        },
+    );
+
+    // XCard matches a xCard (RFC 6351) file, the XML representation of a vCard.
+    let xcard = Mime::new(
+        "application/vcard+xml".to_string(),
+        ".xml".to_string(),
+        XmlDetector {
+            sigs: vec![XmlSig::new(
+                "vcards",
+                r#"xmlns="urn:ietf:params:xml:ns:vcard-4.0""#,
+            )],
+        },
+    );
+
+    // Ttml matches a Timed Text Markup Language file, including its older DFXP profile.
+    let ttml = Mime::new(
+        "application/ttml+xml".to_string(),
+        ".ttml".to_string(),
+        XmlDetector {
+            sigs: vec![
+                XmlSig::new("tt", r#"xmlns="http://www.w3.org/ns/ttml""#),
+                XmlSig::new("tt", r#"xmlns="http://www.w3.org/2006/10/ttaf1""#),
+                XmlSig::new("tt", r#"xmlns="http://www.w3.org/2006/04/ttaf1""#),
+            ],
+        },
+    )
+    .aliases(vec!["application/ttaf+xml"]);
+
     // XML matches an Extensible Markup Language file.
     let xml = Mime::new(
         "text/xml".to_string(),
@@ -1348,7 +2309,12 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
         MarkUpDetector {
             sigs: vec![b"<?XML"],
         },
-    );
+    )
+    .aliases(vec!["application/xml"])
+    .children(vec![
+        rss, atom, x3d, kml, xliff, collada, gml, gpx, tcx, amf, threemf, xfdf, usf, xcal, xcard,
+        ttml,
+    ]);
 
     // Php matches a PHP: Hypertext Preprocessor file.
     let php = Mime::new("text/x-php".to_string(), ".php".to_string(), PhpDetector {});
@@ -1358,14 +2324,7 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
         "application/javascript".to_string(),
         ".js".to_string(),
         SheBangDetector {
-            sigs: vec![
-                b"/bin/node",
-                b"/usr/bin/node",
-                b"/bin/nodejs",
-                b"/usr/bin/nodejs",
-                b"/usr/bin/env node",
-                b"/usr/bin/env nodejs",
-            ],
+            interpreters: vec!["node", "nodejs", "deno", "bun"],
         },
     )
     .aliases(vec!["application/x-javascript", "text/javascript"]);
@@ -1375,7 +2334,7 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
         "text/x-lua".to_string(),
         ".lua".to_string(),
         SheBangDetector {
-            sigs: vec![b"/usr/bin/lua", b"/usr/local/bin/lua", b"/usr/bin/env lua"],
+            interpreters: vec!["lua", "luajit"],
         },
     );
 
@@ -1384,7 +2343,7 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
         "text/x-perl".to_string(),
         ".pl".to_string(),
         SheBangDetector {
-            sigs: vec![b"/usr/bin/perl", b"/usr/bin/env perl"],
+            interpreters: vec!["perl"],
         },
     );
 
@@ -1393,11 +2352,115 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
         "text/x-python".to_string(),
         ".py".to_string(),
         SheBangDetector {
-            sigs: vec![
-                b"/usr/bin/python",
-                b"/usr/local/bin/python",
-                b"/usr/bin/env python",
-            ],
+            interpreters: vec!["python", "pypy"],
+        },
+    );
+
+    // Shell matches a shell script file.
+    let shell = Mime::new(
+        "text/x-shellscript".to_string(),
+        ".sh".to_string(),
+        SheBangDetector {
+            interpreters: vec!["sh", "bash", "dash", "ash", "ksh", "mksh", "zsh", "busybox"],
+        },
+    )
+    .aliases(vec![
+        "text/x-sh",
+        "application/x-shellscript",
+        "application/x-sh",
+    ]);
+
+    // Csh matches a C shell script file.
+    let csh = Mime::new(
+        "application/x-csh".to_string(),
+        ".csh".to_string(),
+        SheBangDetector {
+            interpreters: vec!["csh", "tcsh"],
+        },
+    );
+
+    // Fish matches a fish shell script file.
+    let fish = Mime::new(
+        "text/x-fish".to_string(),
+        ".fish".to_string(),
+        SheBangDetector {
+            interpreters: vec!["fish"],
+        },
+    );
+
+    // Ruby matches a Ruby programming language file.
+    let ruby = Mime::new(
+        "text/x-ruby".to_string(),
+        ".rb".to_string(),
+        SheBangDetector {
+            interpreters: vec!["ruby", "jruby"],
+        },
+    )
+    .aliases(vec!["application/x-ruby"]);
+
+    // Tcl matches a Tcl programming language file.
+    let tcl = Mime::new(
+        "text/x-tcl".to_string(),
+        ".tcl".to_string(),
+        SheBangDetector {
+            interpreters: vec!["tclsh", "wish", "expect", "jimsh"],
+        },
+    )
+    .aliases(vec!["application/x-tcl"]);
+
+    // Awk matches an AWK program file.
+    let awk = Mime::new(
+        "text/x-awk".to_string(),
+        ".awk".to_string(),
+        SheBangDetector {
+            interpreters: vec!["awk", "gawk", "mawk", "nawk"],
+        },
+    );
+
+    // PowerShell matches a PowerShell script file.
+    let powershell = Mime::new(
+        "text/x-powershell".to_string(),
+        ".ps1".to_string(),
+        SheBangDetector {
+            interpreters: vec!["pwsh", "powershell"],
+        },
+    )
+    .aliases(vec!["application/x-powershell"]);
+
+    // Groovy matches a Groovy programming language file.
+    let groovy = Mime::new(
+        "text/x-groovy".to_string(),
+        ".groovy".to_string(),
+        SheBangDetector {
+            interpreters: vec!["groovy"],
+        },
+    );
+
+    // R matches an R programming language file.
+    let r = Mime::new(
+        "text/x-r".to_string(),
+        ".r".to_string(),
+        SheBangDetector {
+            interpreters: vec!["Rscript", "R"],
+        },
+    )
+    .aliases(vec!["text/x-rsrc"]);
+
+    // Julia matches a Julia programming language file.
+    let julia = Mime::new(
+        "text/x-julia".to_string(),
+        ".jl".to_string(),
+        SheBangDetector {
+            interpreters: vec!["julia"],
+        },
+    );
+
+    // Elixir matches an Elixir script file.
+    let elixir = Mime::new(
+        "text/x-elixir".to_string(),
+        ".exs".to_string(),
+        SheBangDetector {
+            interpreters: vec!["elixir"],
         },
     );
 
@@ -1408,13 +2471,356 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
         HarDetector {},
     );
 
+    // TopoJSON matches a TopoJSON file.
+    let topojson = Mime::new(
+        "application/topo+json".to_string(),
+        ".topojson".to_string(),
+        TopoJsonDetector {},
+    );
+
+    // JsonSchema matches a JSON Schema file.
+    let json_schema = Mime::new(
+        "application/schema+json".to_string(),
+        ".json".to_string(),
+        JsonSchemaDetector {},
+    );
+
+    // OpenApi matches an OpenAPI or Swagger description file.
+    let openapi = Mime::new(
+        "application/vnd.oai.openapi+json".to_string(),
+        ".json".to_string(),
+        OpenApiDetector {},
+    );
+
+    // Ipynb matches a Jupyter notebook file.
+    let ipynb = Mime::new(
+        "application/x-ipynb+json".to_string(),
+        ".ipynb".to_string(),
+        IpynbDetector {},
+    );
+
+    // Gltf matches a glTF model file in its JSON representation.
+    let gltf = Mime::new(
+        "model/gltf+json".to_string(),
+        ".gltf".to_string(),
+        GltfDetector {},
+    );
+
+    // WebExtensionManifest matches a Chrome or Firefox extension manifest file.
+    let web_extension_manifest = Mime::new(
+        "application/x-web-extension-manifest+json".to_string(),
+        ".json".to_string(),
+        WebExtensionManifestDetector {},
+    );
+
+    // WebAppManifest matches a Web Application Manifest file.
+    let web_app_manifest = Mime::new(
+        "application/manifest+json".to_string(),
+        ".webmanifest".to_string(),
+        WebAppManifestDetector {},
+    );
+
+    // ActivityStreams matches an Activity Streams 2.0 document.
+    let activity_streams = Mime::new(
+        "application/activity+json".to_string(),
+        ".json".to_string(),
+        ActivityStreamsDetector {},
+    );
+
+    // JsonLd matches a JSON-LD document.
+    let json_ld = Mime::new(
+        "application/ld+json".to_string(),
+        ".jsonld".to_string(),
+        JsonLdDetector {},
+    )
+    .children(vec![activity_streams]);
+
+    // JCard matches a jCard file, the JSON representation of a vCard.
+    let jcard = Mime::new(
+        "application/vcard+json".to_string(),
+        ".jcard".to_string(),
+        JCardDetector {},
+    );
+
+    // JCal matches a jCal file, the JSON representation of an iCalendar.
+    let jcal = Mime::new(
+        "application/calendar+json".to_string(),
+        ".jcal".to_string(),
+        JCalDetector {},
+    );
+
     // JSON matches a JavaScript Object Notation file.
     let json = Mime::new(
         "application/json".to_string(),
         ".json".to_string(),
         JsonDetector {},
     )
-    .children(vec![geojson, har]);
+    .children(vec![
+        geojson,
+        topojson,
+        har,
+        json_schema,
+        openapi,
+        ipynb,
+        gltf,
+        web_extension_manifest,
+        web_app_manifest,
+        json_ld,
+        jcard,
+        jcal,
+    ]);
+
+    // NdJson matches a Newline delimited JSON file.
+    let ndjson = Mime::new(
+        "application/x-ndjson".to_string(),
+        ".ndjson".to_string(),
+        NdJsonDetector {},
+    )
+    .aliases(vec!["application/jsonl"]);
+
+    // VCard matches a Virtual Contact File.
+    let vcard = Mime::new(
+        "text/vcard".to_string(),
+        ".vcf".to_string(),
+        VCardDetector {},
+    )
+    .aliases(vec!["text/x-vcard"])
+    .params(vcard_params);
+
+    // ICalendar matches a iCalendar file.
+    let icalendar = Mime::new(
+        "text/calendar".to_string(),
+        ".ics".to_string(),
+        ICalendarDetector {},
+    );
+
+    // Vtt matches a Web Video Text Tracks (WebVTT) file.
+    let vtt = Mime::new("text/vtt".to_string(), ".vtt".to_string(), VttDetector {});
+
+    // Srt matches a SubRip file.
+    let srt = Mime::new(
+        "application/x-subrip".to_string(),
+        ".srt".to_string(),
+        SrtDetector {},
+    )
+    .aliases(vec!["application/x-srt", "text/x-srt"]);
+
+    // Ass matches an Advanced SubStation Alpha subtitle file.
+    let ass = Mime::new("text/x-ass".to_string(), ".ass".to_string(), AssDetector {});
+
+    // Ssa matches a SubStation Alpha subtitle file.
+    let ssa =
+        Mime::new("text/x-ssa".to_string(), ".ssa".to_string(), SsaDetector {}).children(vec![ass]);
+
+    // Sami matches a Synchronized Accessible Media Interchange file.
+    let sami = Mime::new(
+        "application/x-sami".to_string(),
+        ".smi".to_string(),
+        MarkUpDetector {
+            sigs: vec![b"<SAMI"],
+        },
+    );
+
+    // Mbox matches a mailbox file.
+    let mbox = Mime::new(
+        "application/mbox".to_string(),
+        ".mbox".to_string(),
+        MboxDetector {},
+    );
+
+    // Mhtml matches a MIME HTML web archive file.
+    let mhtml = Mime::new(
+        "multipart/related".to_string(),
+        ".mht".to_string(),
+        MhtmlDetector {},
+    )
+    .aliases(vec!["application/x-mimearchive"]);
+
+    // Eml matches an Internet Message Format file.
+    let eml = Mime::new(
+        "message/rfc822".to_string(),
+        ".eml".to_string(),
+        EmlDetector {},
+    )
+    .children(vec![mhtml]);
+
+    // FormData matches a multipart/form-data body.
+    let form_data = Mime::new(
+        "multipart/form-data".to_string(),
+        "".to_string(),
+        FormDataDetector {},
+    );
+
+    // Multipart matches a MIME multipart body.
+    let multipart = Mime::new(
+        "multipart/mixed".to_string(),
+        "".to_string(),
+        MultipartDetector {},
+    )
+    .children(vec![form_data]);
+
+    // Csv matches a comma-separated values file.
+    let csv = Mime::new("text/csv".to_string(), ".csv".to_string(), CsvDetector {});
+
+    // Tsv matches a tab-separated values file.
+    let tsv = Mime::new(
+        "text/tab-separated-values".to_string(),
+        ".tsv".to_string(),
+        TsvDetector {},
+    );
+
+    // Yaml matches a YAML file.
+    let yaml = Mime::new(
+        "application/yaml".to_string(),
+        ".yaml".to_string(),
+        YamlDetector {},
+    )
+    .aliases(vec!["application/x-yaml", "text/yaml", "text/x-yaml"]);
+
+    // Toml matches a TOML file.
+    let toml = Mime::new(
+        "application/toml".to_string(),
+        ".toml".to_string(),
+        TomlDetector {},
+    );
+
+    // Ini matches an INI configuration file.
+    let ini = Mime::new("text/x-ini".to_string(), ".ini".to_string(), IniDetector {});
+
+    // Properties matches a Java properties file.
+    let properties = Mime::new(
+        "text/x-java-properties".to_string(),
+        ".properties".to_string(),
+        PropertiesDetector {},
+    );
+
+    // Markdown matches a Markdown file.
+    let markdown = Mime::new(
+        "text/markdown".to_string(),
+        ".md".to_string(),
+        MarkdownDetector {},
+    )
+    .aliases(vec!["text/x-markdown"]);
+
+    // C matches a C source code file.
+    let c = Mime::new(
+        Language::C.mime().to_string(),
+        Language::C.extension().to_string(),
+        LanguageDetector {
+            language: Language::C,
+        },
+    );
+
+    // Cpp matches a C++ source code file.
+    let cpp = Mime::new(
+        Language::Cpp.mime().to_string(),
+        Language::Cpp.extension().to_string(),
+        LanguageDetector {
+            language: Language::Cpp,
+        },
+    );
+
+    // Rust matches a Rust source code file.
+    let rust = Mime::new(
+        Language::Rust.mime().to_string(),
+        Language::Rust.extension().to_string(),
+        LanguageDetector {
+            language: Language::Rust,
+        },
+    );
+
+    // Go matches a Go source code file.
+    let go = Mime::new(
+        Language::Go.mime().to_string(),
+        Language::Go.extension().to_string(),
+        LanguageDetector {
+            language: Language::Go,
+        },
+    );
+
+    // Java matches a Java source code file.
+    let java = Mime::new(
+        Language::Java.mime().to_string(),
+        Language::Java.extension().to_string(),
+        LanguageDetector {
+            language: Language::Java,
+        },
+    );
+
+    // CSharp matches a C# source code file.
+    let csharp = Mime::new(
+        Language::CSharp.mime().to_string(),
+        Language::CSharp.extension().to_string(),
+        LanguageDetector {
+            language: Language::CSharp,
+        },
+    );
+
+    // TypeScript matches a TypeScript source code file.
+    let typescript = Mime::new(
+        Language::TypeScript.mime().to_string(),
+        Language::TypeScript.extension().to_string(),
+        LanguageDetector {
+            language: Language::TypeScript,
+        },
+    );
+
+    // Sql matches a SQL script file.
+    let sql = Mime::new(
+        Language::Sql.mime().to_string(),
+        Language::Sql.extension().to_string(),
+        LanguageDetector {
+            language: Language::Sql,
+        },
+    );
+
+    // Shell matches a shell script file without a shebang.
+    let shell_source = Mime::new(
+        Language::Shell.mime().to_string(),
+        Language::Shell.extension().to_string(),
+        LanguageDetector {
+            language: Language::Shell,
+        },
+    );
+
+    // Makefile matches a Makefile.
+    let makefile = Mime::new(
+        Language::Makefile.mime().to_string(),
+        Language::Makefile.extension().to_string(),
+        LanguageDetector {
+            language: Language::Makefile,
+        },
+    );
+
+    // Dockerfile matches a Dockerfile.
+    let dockerfile = Mime::new(
+        Language::Dockerfile.mime().to_string(),
+        Language::Dockerfile.extension().to_string(),
+        LanguageDetector {
+            language: Language::Dockerfile,
+        },
+    );
+
+    // Source matches a source code file, when the source code classifier is
+    // enabled. It selects the child of the language itself.
+    let source = Mime::new(
+        "text/plain".to_string(),
+        ".txt".to_string(),
+        SourceDetector {},
+    )
+    .children(vec![
+        c,
+        cpp,
+        rust,
+        go,
+        java,
+        csharp,
+        typescript,
+        sql,
+        shell_source,
+        makefile,
+        dockerfile,
+    ]);
 
     // Keep text last because it is the slowest check
     let text = Mime::new(
@@ -1422,23 +2828,37 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
         ".txt".to_string(),
         TextDetector {},
     )
-    .children(vec![html, svg, xml, php, js, lua, perl, json, python, rtf]);
-
-    Mime::new(
-        "application/octet-stream".to_string(),
-        "".to_string(),
-        EmptyDetector {},
-    )
     .children(vec![
+        html, svg, xml, php, js, lua, perl, json, ndjson, python, shell, csh, fish, ruby, tcl, awk,
+        powershell, groovy, r, julia, elixir, rtf, vcard, icalendar, vtt, srt, ssa, sami, mbox,
+        eml, multipart, yaml, toml, ini, properties, markdown, source, csv, tsv,
+    ]);
+
+    let mut children = vec![
         xpm,
         seven_z,
         zip,
         pdf,
         fdf,
         ole,
+        pst,
         ps,
         psd,
         p7s,
+        x509_certificate,
+        x509_crl,
+        pkcs8_der,
+        pkcs8_encrypted_der,
+        pkcs12,
+        timestamp_query,
+        timestamp_reply,
+        pgp_message,
+        pgp_public_key,
+        pgp_private_key,
+        pgp_signature,
+        pem,
+        ppk,
+        ssh_public_key,
         ogg,
         png,
         jpg,
@@ -1448,7 +2868,7 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
         jpm,
         jxs,
         gif,
-        webp,
+        riff,
         exe,
         elf,
         ar,
@@ -1465,17 +2885,21 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
         ape,
         musepack,
         amr,
-        wav,
         aiff,
         au,
         mpeg,
         quicktime,
         mqv,
+        cr3,
+        avif,
+        heic,
+        heic_sequence,
+        heif,
+        heif_sequence,
         mp4,
         webm,
         three_gp,
         three_g2,
-        avi,
         flv,
         mkv,
         asf,
@@ -1497,6 +2921,17 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
         ttc,
         eot,
         wasm,
+        pyc,
+        lua_bytecode,
+        dex,
+        odex,
+        vdex,
+        beam,
+        ocaml_interface,
+        ocaml_library,
+        ocaml_object,
+        ocaml_exe,
+        java_serialized,
         shx,
         dbf,
         dcm,
@@ -1510,12 +2945,7 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
         nes,
         lnk,
         macho,
-        qcp,
         icns,
-        heic,
-        heic_sequence,
-        heif,
-        heif_sequence,
         hdr,
         marc,
         ms_access_mdb,
@@ -1532,9 +2962,165 @@ pub(crate) static ROOT: LazyLock<Mime> = LazyLock::new(|| {
         pat,
         gbr,
         glb,
-        avif,
         installshield_cab,
         jxr,
-        text,
-    ])
+    ];
+    children.extend(disk_images());
+    children.push(text);
+
+    Mime::new(
+        "application/octet-stream".to_string(),
+        "".to_string(),
+        EmptyDetector {},
+    )
+    .children(children)
 });
+
+// DISK_IMAGE is the tree of the disk and virtual machine image formats, whose
+// signatures may lie far from the start of the file or at its end.
+pub(crate) static DISK_IMAGE: LazyLock<Mime> = LazyLock::new(|| {
+    Mime::new(
+        "application/octet-stream".to_string(),
+        "".to_string(),
+        EmptyDetector {},
+    )
+    .children(disk_images())
+});
+
+fn disk_images() -> Vec<Mime> {
+    // Udf matches a Universal Disk Format image.
+    let udf = Mime::new(
+        "application/x-udf-image".to_string(),
+        ".iso".to_string(),
+        UdfDetector {},
+    );
+
+    // Iso9660 matches an ISO 9660 CD or DVD image.
+    let iso9660 = Mime::new(
+        "application/x-iso9660-image".to_string(),
+        ".iso".to_string(),
+        Iso9660Detector {},
+    )
+    .aliases(vec!["application/x-cd-image"])
+    .children(vec![udf]);
+
+    // Dmg matches an Apple disk image, which ends with a koly trailer.
+    let dmg = Mime::new(
+        "application/x-apple-diskimage".to_string(),
+        ".dmg".to_string(),
+        TrailerDetector {
+            offset: 512,
+            sig: b"koly",
+        },
+    );
+
+    // Vhd matches a Microsoft Virtual Hard Disk file.
+    let vhd = Mime::new(
+        "application/x-vhd-disk".to_string(),
+        ".vhd".to_string(),
+        VhdDetector {},
+    );
+
+    // Vhdx matches a Microsoft Virtual Hard Disk v2 file.
+    let vhdx = Mime::new(
+        "application/x-vhdx-disk".to_string(),
+        ".vhdx".to_string(),
+        PrefixDetector {
+            sigs: vec![b"vhdxfile"],
+        },
+    );
+
+    // Vdi matches a VirtualBox Disk Image file.
+    let vdi = Mime::new(
+        "application/x-virtualbox-vdi".to_string(),
+        ".vdi".to_string(),
+        VdiDetector {},
+    );
+
+    // Qcow matches a QEMU copy-on-write disk image.
+    let qcow = Mime::new(
+        "application/x-qemu-disk".to_string(),
+        ".qcow2".to_string(),
+        PrefixDetector {
+            sigs: vec![b"QFI\xFB"],
+        },
+    )
+    .params(qcow_params);
+
+    // Vmdk matches a VMware virtual disk file.
+    let vmdk = Mime::new(
+        "application/x-vmdk-disk".to_string(),
+        ".vmdk".to_string(),
+        VmdkDetector {},
+    );
+
+    // SquashFs matches a SquashFS filesystem image.
+    let squashfs = Mime::new(
+        "application/vnd.squashfs".to_string(),
+        ".sqsh".to_string(),
+        SquashFsDetector {},
+    )
+    .params(squashfs_params);
+
+    // Luks matches a LUKS encrypted volume, of version 1 or 2.
+    let luks = Mime::new(
+        "application/x-raw-disk-image".to_string(),
+        ".img".to_string(),
+        PrefixDetector {
+            sigs: vec![b"LUKS\xBA\xBE\x00\x01", b"LUKS\xBA\xBE\x00\x02"],
+        },
+    )
+    .params(raw_image_params);
+
+    // Ext matches an ext2, ext3 or ext4 filesystem image.
+    let ext = Mime::new(
+        "application/x-raw-disk-image".to_string(),
+        ".img".to_string(),
+        ExtDetector {},
+    )
+    .params(raw_image_params);
+
+    // Ntfs matches an NTFS filesystem image.
+    let ntfs = Mime::new(
+        "application/x-raw-disk-image".to_string(),
+        ".img".to_string(),
+        BootSectorDetector { oem: b"NTFS    " },
+    )
+    .params(raw_image_params);
+
+    // ExFat matches an exFAT filesystem image.
+    let exfat = Mime::new(
+        "application/x-raw-disk-image".to_string(),
+        ".img".to_string(),
+        BootSectorDetector { oem: b"EXFAT   " },
+    )
+    .params(raw_image_params);
+
+    // Fat matches a FAT filesystem image.
+    let fat = Mime::new(
+        "application/x-raw-disk-image".to_string(),
+        ".img".to_string(),
+        FatDetector {},
+    )
+    .params(raw_image_params);
+
+    // Gpt matches a disk image with a GUID partition table.
+    let gpt = Mime::new(
+        "application/x-raw-disk-image".to_string(),
+        ".img".to_string(),
+        GptDetector {},
+    )
+    .params(raw_image_params);
+
+    // Mbr matches a disk image with a master boot record partition table.
+    let mbr = Mime::new(
+        "application/x-raw-disk-image".to_string(),
+        ".img".to_string(),
+        MbrDetector {},
+    )
+    .params(raw_image_params);
+
+    vec![
+        iso9660, dmg, vhd, vhdx, vdi, qcow, vmdk, squashfs, luks, ext, ntfs, exfat, fat, gpt, mbr,
+    ]
+}
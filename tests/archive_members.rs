@@ -0,0 +1,176 @@
+mod common;
+
+use common::{check_prefixes, zip, ZipEntry};
+use mimetype::{archive_members, ArchiveLimits, ArchiveMember};
+
+// count returns the number of members, nested members included.
+fn count(members: &[ArchiveMember]) -> usize {
+    members.iter().map(|m| 1 + count(&m.members)).sum()
+}
+
+// nested returns a zip archive holding a text file, nested depth zips deep.
+fn nested(depth: usize) -> Vec<u8> {
+    let mut archive = zip(&[ZipEntry::stored("leaf.txt", b"hello world\n")]);
+    for _ in 0..depth {
+        archive = zip(&[ZipEntry::stored("inner.zip", &archive)]);
+    }
+    archive
+}
+
+#[test]
+fn walks_members() {
+    let archive = zip(&[
+        ZipEntry::stored("a.txt", b"hello world\n"),
+        ZipEntry::stored("b.zip", &nested(0)),
+    ]);
+    let members = archive_members(&archive, &ArchiveLimits::default()).unwrap();
+    assert_eq!(members.len(), 2);
+    assert_eq!(members[0].name, "a.txt");
+    assert_eq!(members[0].mime.as_ref().unwrap().mime, "text/plain");
+    assert_eq!(members[1].mime.as_ref().unwrap().mime, "application/zip");
+    assert_eq!(members[1].members[0].name, "leaf.txt");
+
+    assert!(archive_members(b"hello world\n", &ArchiveLimits::default()).is_none());
+}
+
+#[test]
+fn stops_at_max_members() {
+    let data = b"hello world\n";
+    let names: Vec<String> = (0..50).map(|i| format!("{}.txt", i)).collect();
+    let entries: Vec<ZipEntry> = names.iter().map(|n| ZipEntry::stored(n, data)).collect();
+    let limits = ArchiveLimits {
+        max_members: 10,
+        ..Default::default()
+    };
+    let members = archive_members(&zip(&entries), &limits).unwrap();
+    assert_eq!(members.len(), 10);
+
+    // Nested members count towards the limit too.
+    let members = archive_members(&nested(20), &limits).unwrap();
+    assert_eq!(count(&members), 5);
+}
+
+#[test]
+fn stops_at_max_depth() {
+    let limits = ArchiveLimits {
+        max_depth: 2,
+        ..Default::default()
+    };
+    let members = archive_members(&nested(5), &limits).unwrap();
+    // The top level and two nested levels are walked.
+    assert_eq!(count(&members), 3);
+    assert!(members[0].members[0].members[0].members.is_empty());
+}
+
+#[test]
+fn skips_members_over_max_member_size() {
+    let limits = ArchiveLimits {
+        max_member_size: 64,
+        ..Default::default()
+    };
+    let members = archive_members(&nested(2), &limits).unwrap();
+    assert_eq!(members.len(), 1);
+    assert_eq!(members[0].mime.as_ref().unwrap().mime, "application/zip");
+    assert!(members[0].members.is_empty());
+}
+
+#[test]
+fn reports_sizes_as_recorded() {
+    // A member claiming a huge size is neither trusted nor read past the archive.
+    let archive = zip(&[ZipEntry {
+        name: "bomb.bin",
+        data: b"hello world\n",
+        size: u32::MAX,
+        method: 0,
+    }]);
+    let members = archive_members(&archive, &ArchiveLimits::default()).unwrap();
+    assert_eq!(members[0].size, u32::MAX as u64);
+    assert!(members[0].members.is_empty());
+}
+
+#[cfg(feature = "decompress")]
+#[test]
+fn stops_at_max_total_size() {
+    use std::io::Write;
+
+    // A zip bomb in miniature: each member inflates to 1 MiB of zeros.
+    let mut encoder =
+        flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&vec![0; 1 << 20]).unwrap();
+    let deflated = encoder.finish().unwrap();
+    let names: Vec<String> = (0..8).map(|i| format!("{}.bin", i)).collect();
+    let entries: Vec<ZipEntry> = names
+        .iter()
+        .map(|name| ZipEntry {
+            name,
+            data: &deflated,
+            size: 1 << 20,
+            method: 8,
+        })
+        .collect();
+    let limits = ArchiveLimits {
+        max_total_size: 5000,
+        ..Default::default()
+    };
+    let members = archive_members(&zip(&entries), &limits).unwrap();
+    assert_eq!(members.len(), 8);
+    // The first members use up the budget, 3072 bytes each by default; the
+    // others are listed without being inflated.
+    assert!(members[0].mime.is_some());
+    assert!(members[1].mime.is_some());
+    assert!(members[2..].iter().all(|m| m.mime.is_none()));
+}
+
+#[test]
+fn truncated_archives() {
+    check_prefixes(&zip(&[
+        ZipEntry::stored("a.txt", b"hello world\n"),
+        ZipEntry::stored("b.zip", &nested(1)),
+    ]));
+
+    let mut tar = vec![0; 512];
+    tar[..5].copy_from_slice(b"a.txt");
+    tar[100..107].copy_from_slice(b"0000644");
+    tar[124..135].copy_from_slice(b"00000000014");
+    tar[156] = b'0';
+    tar[257..263].copy_from_slice(b"ustar\0");
+    tar[148..156].copy_from_slice(b"        ");
+    let sum: u32 = tar.iter().map(|&b| b as u32).sum();
+    tar[148..155].copy_from_slice(format!("{:06o}\0", sum).as_bytes());
+    tar.extend_from_slice(b"hello world\n");
+    tar.resize(2048, 0);
+    assert_eq!(
+        archive_members(&tar, &ArchiveLimits::default()).unwrap()[0].name,
+        "a.txt"
+    );
+    check_prefixes(&tar);
+
+    let mut ar = b"!<arch>\n".to_vec();
+    ar.extend_from_slice(
+        format!(
+            "{:<16}{:<12}{:<6}{:<6}{:<8}{:<10}`\n",
+            "a.txt/", 0, 0, 0, 644, 12
+        )
+        .as_bytes(),
+    );
+    ar.extend_from_slice(b"hello world\n");
+    assert_eq!(
+        archive_members(&ar, &ArchiveLimits::default()).unwrap()[0].name,
+        "a.txt"
+    );
+    check_prefixes(&ar);
+
+    let mut cpio = format!(
+        "070701{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}",
+        1, 0o100644, 0, 0, 1, 0, 12, 0, 0, 0, 0, 6, 0
+    )
+    .into_bytes();
+    cpio.extend_from_slice(b"a.txt\0");
+    cpio.resize(cpio.len().next_multiple_of(4), 0);
+    cpio.extend_from_slice(b"hello world\n");
+    assert_eq!(
+        archive_members(&cpio, &ArchiveLimits::default()).unwrap()[0].name,
+        "a.txt"
+    );
+    check_prefixes(&cpio);
+}
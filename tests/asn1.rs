@@ -0,0 +1,43 @@
+// DER certificates and keys, and no panic on any prefix of them.
+mod common;
+
+use common::{check_prefixes, der, mime};
+
+#[test]
+fn asn1() {
+    let oid = der(0x06, b"\x2A\x86\x48\x86\xF7\x0D\x01\x01\x0B");
+    let algorithm = der(0x30, &oid);
+    let name = der(
+        0x30,
+        &der(0x31, &der(0x30, &[oid.clone(), der(0x0C, b"x")].concat())),
+    );
+    let validity = der(
+        0x30,
+        &[der(0x17, b"250101000000Z"), der(0x17, b"350101000000Z")].concat(),
+    );
+    let tbs = der(
+        0x30,
+        &[
+            der(0xA0, &der(0x02, &[2])),
+            der(0x02, &[1]),
+            algorithm.clone(),
+            name.clone(),
+            validity,
+            name,
+        ]
+        .concat(),
+    );
+    let cert = der(0x30, &[tbs, algorithm.clone(), der(0x03, &[0; 9])].concat());
+    assert_eq!(mime(&cert), "application/pkix-cert");
+    check_prefixes(&cert);
+
+    let key = der(
+        0x30,
+        &[der(0x02, &[0]), algorithm, der(0x04, &[0; 32])].concat(),
+    );
+    assert_eq!(mime(&key), "application/pkcs8");
+    check_prefixes(&key);
+
+    // Indefinite lengths, as BER allows.
+    check_prefixes(b"\x30\x80\x30\x80\x02\x01\x01\0\0\0\0");
+}
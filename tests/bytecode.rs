@@ -0,0 +1,46 @@
+// Bytecode formats and their versions.
+mod common;
+
+use common::{check_prefixes, mime, pairs, params};
+
+#[test]
+fn bytecode() {
+    let cases: [(&[u8], &str, Option<&str>); 6] = [
+        (
+            b"\xCB\x0D\r\n\0\0\0\0\0\0\0\0\0\0\0\0\xE3",
+            "application/x-python-code",
+            Some("3.12"),
+        ),
+        (
+            b"\x1BLua\x54\0\x19\x93\r\n\x1A\n\x04\x08\x08",
+            "application/x-lua-bytecode",
+            Some("5.4"),
+        ),
+        (
+            b"dex\n035\0\0\0\0\0\0\0\0\0",
+            "application/vnd.android.dex",
+            Some("035"),
+        ),
+        (
+            b"FOR1\0\0\x01\0BEAMAtU8\0\0\0\0",
+            "application/x-erlang-binary",
+            None,
+        ),
+        (
+            b"Caml1999O034\0\0\0\0",
+            "application/x-ocaml-bytecode",
+            Some("034"),
+        ),
+        (
+            b"\xAC\xED\0\x05\x73\x72\0\x04Test",
+            "application/x-java-serialized-object",
+            None,
+        ),
+    ];
+    for (content, expected, version) in cases {
+        assert_eq!(mime(content), expected);
+        let version: Vec<_> = version.map(|v| ("version", v)).into_iter().collect();
+        assert_eq!(params(content), pairs(&version));
+        check_prefixes(content);
+    }
+}
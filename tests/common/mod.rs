@@ -0,0 +1,127 @@
+// Helpers shared by the integration tests: builders for the binary layouts the
+// fixtures are made of, and the truncation check.
+#![allow(dead_code)]
+
+use mimetype::ArchiveLimits;
+
+// check_prefixes runs the public entry points on every prefix of content. None
+// of them may panic, whatever the input is cut short at.
+pub fn check_prefixes(content: &[u8]) {
+    for n in 0..=content.len() {
+        let prefix = &content[..n];
+        mimetype::detect(prefix);
+        mimetype::image_info(prefix);
+        mimetype::detect_disk_image(prefix);
+        mimetype::archive_members(prefix, &ArchiveLimits::default());
+        #[cfg(feature = "decompress")]
+        mimetype::detect_nested(prefix);
+    }
+}
+
+// mime returns the MIME type detected for content, without its parameters.
+pub fn mime(content: &[u8]) -> String {
+    mimetype::detect(content).mime
+}
+
+// params returns the parameters of the MIME type detected for content.
+pub fn params(content: &[u8]) -> Vec<(String, String)> {
+    mimetype::detect(content).params
+}
+
+// pairs turns parameters written as string slices into the owned form of
+// Mime::params.
+pub fn pairs(params: &[(&str, &str)]) -> Vec<(String, String)> {
+    params
+        .iter()
+        .map(|&(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+// bmff returns an ISO base media file format box of the given type.
+pub fn bmff(typ: &[u8; 4], data: &[u8]) -> Vec<u8> {
+    let mut b = ((data.len() + 8) as u32).to_be_bytes().to_vec();
+    b.extend_from_slice(typ);
+    b.extend_from_slice(data);
+    b
+}
+
+// der returns a DER encoded value with the given tag.
+pub fn der(tag: u8, value: &[u8]) -> Vec<u8> {
+    let mut b = vec![tag];
+    match value.len() {
+        n if n < 0x80 => b.push(n as u8),
+        n if n <= 0xFF => b.extend_from_slice(&[0x81, n as u8]),
+        n => {
+            b.push(0x82);
+            b.extend_from_slice(&(n as u16).to_be_bytes());
+        }
+    }
+    b.extend_from_slice(value);
+    b
+}
+
+// ZipEntry is a member of a zip archive built by zip.
+pub struct ZipEntry<'a> {
+    pub name: &'a str,
+    // The data as stored in the archive.
+    pub data: &'a [u8],
+    // The uncompressed size, which differs from the stored size for deflated
+    // members.
+    pub size: u32,
+    pub method: u16,
+}
+
+impl<'a> ZipEntry<'a> {
+    pub fn stored(name: &'a str, data: &'a [u8]) -> Self {
+        ZipEntry {
+            name,
+            data,
+            size: data.len() as u32,
+            method: 0,
+        }
+    }
+}
+
+// zip returns a zip archive of entries, with local file headers, a central
+// directory and its end record. CRCs are left out; nothing checks them.
+pub fn zip(entries: &[ZipEntry]) -> Vec<u8> {
+    let (mut out, mut central) = (vec![], vec![]);
+    for e in entries {
+        let offset = out.len() as u32;
+        let name = e.name.as_bytes();
+        out.extend_from_slice(b"PK\x03\x04");
+        out.extend_from_slice(&20u16.to_le_bytes());
+        out.extend_from_slice(&0u16.to_le_bytes());
+        out.extend_from_slice(&e.method.to_le_bytes());
+        out.extend_from_slice(&[0; 8]);
+        out.extend_from_slice(&(e.data.len() as u32).to_le_bytes());
+        out.extend_from_slice(&e.size.to_le_bytes());
+        out.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        out.extend_from_slice(&0u16.to_le_bytes());
+        out.extend_from_slice(name);
+        out.extend_from_slice(e.data);
+
+        central.extend_from_slice(b"PK\x01\x02");
+        central.extend_from_slice(&20u16.to_le_bytes());
+        central.extend_from_slice(&20u16.to_le_bytes());
+        central.extend_from_slice(&0u16.to_le_bytes());
+        central.extend_from_slice(&e.method.to_le_bytes());
+        central.extend_from_slice(&[0; 8]);
+        central.extend_from_slice(&(e.data.len() as u32).to_le_bytes());
+        central.extend_from_slice(&e.size.to_le_bytes());
+        central.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        central.extend_from_slice(&[0; 12]);
+        central.extend_from_slice(&offset.to_le_bytes());
+        central.extend_from_slice(name);
+    }
+    let (cd_offset, cd_size) = (out.len() as u32, central.len() as u32);
+    out.extend_from_slice(&central);
+    out.extend_from_slice(b"PK\x05\x06");
+    out.extend_from_slice(&[0; 4]);
+    out.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+    out.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+    out.extend_from_slice(&cd_size.to_le_bytes());
+    out.extend_from_slice(&cd_offset.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes());
+    out
+}
@@ -0,0 +1,95 @@
+// CSV and TSV dialects sniffed by detect_csv_dialect.
+mod common;
+
+use common::mime;
+use mimetype::{detect_csv_dialect, CsvDialect, LineTerminator};
+
+fn dialect(delimiter: u8, quote: Option<u8>, has_header: bool) -> CsvDialect {
+    CsvDialect {
+        delimiter,
+        quote,
+        has_header,
+        line_terminator: LineTerminator::Lf,
+    }
+}
+
+#[test]
+fn delimiters() {
+    let csv = b"name,age\nalice,30\nbob,4\n";
+    assert_eq!(mime(csv), "text/csv");
+    assert_eq!(detect_csv_dialect(csv), Some(dialect(b',', None, true)));
+
+    let ssv = b"name;age\nalice;30\nbob;4\n";
+    assert_eq!(detect_csv_dialect(ssv), Some(dialect(b';', None, true)));
+
+    let tsv = b"name\tage\nalice\t30\nbob\t4\n";
+    assert_eq!(mime(tsv), "text/tab-separated-values");
+    assert_eq!(detect_csv_dialect(tsv), Some(dialect(b'\t', None, true)));
+
+    assert_eq!(detect_csv_dialect(b"hello world\nhow are you\n"), None);
+    // A single record is not enough to tell a dialect.
+    assert_eq!(detect_csv_dialect(b"a,b,c\n"), None);
+}
+
+#[test]
+fn quoted_fields() {
+    // The delimiter inside quotes does not split the field.
+    let csv = b"\"name\",age\n\"Smith, Alice\",30\n\"Jones, Bob\",4\n";
+    assert_eq!(
+        detect_csv_dialect(csv),
+        Some(dialect(b',', Some(b'"'), true))
+    );
+
+    let csv = b"'name',age\n'Smith, Alice',30\n'Jones, Bob',4\n";
+    assert_eq!(
+        detect_csv_dialect(csv),
+        Some(dialect(b',', Some(b'\''), true))
+    );
+}
+
+#[test]
+fn header() {
+    // The first row breaks the pattern of numeric columns.
+    let csv = b"x,y\n1,2\n3,4\n";
+    assert!(detect_csv_dialect(csv).unwrap().has_header);
+    let csv = b"1,2\n3,4\n5,6\n";
+    assert!(!detect_csv_dialect(csv).unwrap().has_header);
+
+    // Or the pattern of columns of the same length.
+    let csv = b"code,country\nFR,FRA\nDE,DEU\n";
+    assert!(detect_csv_dialect(csv).unwrap().has_header);
+    let csv = b"FR,FRA\nDE,DEU\nIT,ITA\n";
+    assert!(!detect_csv_dialect(csv).unwrap().has_header);
+}
+
+#[test]
+fn line_terminators() {
+    let csv = b"name,age\r\nalice,30\r\nbob,4\r\n";
+    assert_eq!(
+        detect_csv_dialect(csv).unwrap().line_terminator,
+        LineTerminator::CrLf
+    );
+    let csv = b"name,age\ralice,30\rbob,4\r";
+    assert_eq!(
+        detect_csv_dialect(csv).unwrap().line_terminator,
+        LineTerminator::Cr
+    );
+}
+
+#[test]
+fn truncated_at_limit() {
+    // Only the first 3072 bytes are read, which cut the last record short; the
+    // incomplete line is left out.
+    let mut csv = b"id,name,score\n".to_vec();
+    let mut i = 0;
+    while csv.len() <= 3072 {
+        csv.extend_from_slice(format!("{},\"user {}\",{}\n", i, i, i * 7).as_bytes());
+        i += 1;
+    }
+    assert_ne!(csv[3071], b'\n');
+    assert_eq!(mime(&csv), "text/csv");
+    assert_eq!(
+        detect_csv_dialect(&csv),
+        Some(dialect(b',', Some(b'"'), true))
+    );
+}
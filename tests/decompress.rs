@@ -0,0 +1,98 @@
+// The payload of compressed streams, detected with the decompress feature.
+#![cfg(feature = "decompress")]
+
+use std::io::Write;
+
+use mimetype::detect_nested;
+
+const TEXT: &[u8] = b"hello world\nthis is the payload\n";
+
+// nested returns the MIME types detect_nested reports for content.
+fn nested(content: &[u8]) -> Vec<String> {
+    detect_nested(content).into_iter().map(|m| m.mime).collect()
+}
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut e = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    e.write_all(data).unwrap();
+    e.finish().unwrap()
+}
+
+fn bzip2(data: &[u8]) -> Vec<u8> {
+    let mut e = bzip2::write::BzEncoder::new(Vec::new(), bzip2::Compression::default());
+    e.write_all(data).unwrap();
+    e.finish().unwrap()
+}
+
+fn xz(data: &[u8]) -> Vec<u8> {
+    let mut e = xz2::write::XzEncoder::new(Vec::new(), 6);
+    e.write_all(data).unwrap();
+    e.finish().unwrap()
+}
+
+fn zstd(data: &[u8]) -> Vec<u8> {
+    zstd::stream::encode_all(data, 0).unwrap()
+}
+
+// lzip returns an lzip member: the LZMA data of a legacy .lzma stream, with an
+// 8 MiB dictionary, behind the lzip header. The trailer is left zeroed.
+fn lzip(data: &[u8]) -> Vec<u8> {
+    let options = xz2::stream::LzmaOptions::new_preset(6).unwrap();
+    let stream = xz2::stream::Stream::new_lzma_encoder(&options).unwrap();
+    let mut e = xz2::write::XzEncoder::new_stream(Vec::new(), stream);
+    e.write_all(data).unwrap();
+    let lzma = e.finish().unwrap();
+    [b"LZIP\x01\x17", &lzma[13..], &[0; 20]].concat()
+}
+
+#[test]
+fn formats() {
+    assert_eq!(nested(&gzip(TEXT)), ["application/gzip", "text/plain"]);
+    assert_eq!(nested(&bzip2(TEXT)), ["application/x-bzip2", "text/plain"]);
+    assert_eq!(nested(&xz(TEXT)), ["application/x-xz", "text/plain"]);
+    assert_eq!(nested(&zstd(TEXT)), ["application/zstd", "text/plain"]);
+    assert_eq!(nested(&lzip(TEXT)), ["application/lzip", "text/plain"]);
+
+    assert_eq!(
+        nested(&gzip(b"{\"a\": [1, 2]}")),
+        ["application/gzip", "application/json"]
+    );
+    assert_eq!(nested(TEXT), ["text/plain"]);
+}
+
+#[test]
+fn nesting() {
+    assert_eq!(
+        nested(&zstd(&xz(&gzip(TEXT)))),
+        [
+            "application/zstd",
+            "application/x-xz",
+            "application/gzip",
+            "text/plain"
+        ]
+    );
+
+    // At most four payloads deep.
+    let mut content = TEXT.to_vec();
+    for _ in 0..6 {
+        content = gzip(&content);
+    }
+    assert_eq!(nested(&content), ["application/gzip"; 5]);
+}
+
+#[test]
+fn truncated_streams() {
+    // The payload decompressed before the stream is cut short is detected.
+    let payload = TEXT.repeat(100);
+    let gz = gzip(&payload);
+    assert_eq!(
+        nested(&gz[..gz.len() - 10]),
+        ["application/gzip", "text/plain"]
+    );
+
+    // Nothing decompressed, nothing nested.
+    assert_eq!(nested(&gz[..10]), ["application/gzip"]);
+    for n in 0..gz.len() {
+        detect_nested(&gz[..n]);
+    }
+}
@@ -0,0 +1,96 @@
+// Disk images, read whole by detect_disk_image, and text sharing their magics.
+mod common;
+
+use common::{check_prefixes, mime, pairs, params};
+use mimetype::detect_disk_image;
+
+#[test]
+fn disk_images() {
+    let mut iso = vec![0; 0x8800 + 2048];
+    iso[0x8000..0x8006].copy_from_slice(b"\x01CD001");
+    iso[0x8800..0x8806].copy_from_slice(b"\xFFCD001");
+    assert_eq!(
+        detect_disk_image(&iso).unwrap().mime,
+        "application/x-iso9660-image"
+    );
+    // Only the prefixes reaching the volume descriptors take the long way.
+    check_prefixes(&iso[..2048]);
+    for n in 0x8000..=iso.len() {
+        detect_disk_image(&iso[..n]);
+    }
+
+    let mut ext4 = vec![0; 2048];
+    ext4[1024 + 56..1024 + 58].copy_from_slice(&0xEF53u16.to_le_bytes());
+    ext4[1024 + 96] = 0x40;
+    let m = detect_disk_image(&ext4).unwrap();
+    assert_eq!(m.mime, "application/x-raw-disk-image");
+    assert_eq!(m.params, pairs(&[("filesystem", "ext4")]));
+    check_prefixes(&ext4);
+    // A revision or a block size which does not exist.
+    let mut bad = ext4.clone();
+    bad[1024 + 76] = 2;
+    assert!(detect_disk_image(&bad).is_none());
+    let mut bad = ext4.clone();
+    bad[1024 + 24] = 7;
+    assert!(detect_disk_image(&bad).is_none());
+
+    let mut gpt = vec![0; 1024];
+    gpt[446 + 4] = 0xEE;
+    gpt[446 + 8] = 1;
+    gpt[446 + 12..446 + 16].copy_from_slice(&u32::MAX.to_le_bytes());
+    gpt[510..512].copy_from_slice(&[0x55, 0xAA]);
+    gpt[512..520].copy_from_slice(b"EFI PART");
+    let m = detect_disk_image(&gpt).unwrap();
+    assert_eq!(m.mime, "application/x-raw-disk-image");
+    assert_eq!(m.params, pairs(&[("partitions", "gpt")]));
+    check_prefixes(&gpt);
+
+    // Two Linux partitions, the first one bootable.
+    let mut mbr = vec![0; 512];
+    for (i, (start, size)) in [(2048u32, 4096u32), (6144, 8192)].into_iter().enumerate() {
+        let e = 446 + i * 16;
+        mbr[e + 4] = 0x83;
+        mbr[e + 8..e + 12].copy_from_slice(&start.to_le_bytes());
+        mbr[e + 12..e + 16].copy_from_slice(&size.to_le_bytes());
+    }
+    mbr[446] = 0x80;
+    mbr[510..512].copy_from_slice(&[0x55, 0xAA]);
+    let m = detect_disk_image(&mbr).unwrap();
+    assert_eq!(m.mime, "application/x-raw-disk-image");
+    assert_eq!(m.params, pairs(&[("partitions", "mbr")]));
+    check_prefixes(&mbr);
+    // Partitions overlapping, or starting on the MBR, make another boot sector.
+    let mut bad = mbr.clone();
+    bad[446 + 16 + 8..446 + 16 + 12].copy_from_slice(&4096u32.to_le_bytes());
+    assert!(detect_disk_image(&bad).is_none());
+    let mut bad = mbr.clone();
+    bad[446 + 8..446 + 12].copy_from_slice(&[0; 4]);
+    assert!(detect_disk_image(&bad).is_none());
+
+    let mut vhd = vec![0; 1024];
+    vhd[512..520].copy_from_slice(b"conectix");
+    assert_eq!(
+        detect_disk_image(&vhd).unwrap().mime,
+        "application/x-vhd-disk"
+    );
+    check_prefixes(&vhd);
+
+    let mut squashfs = b"hsqs".to_vec();
+    squashfs.resize(96, 0);
+    squashfs[28] = 4;
+    assert_eq!(mime(&squashfs), "application/vnd.squashfs");
+    assert_eq!(params(&squashfs), pairs(&[("version", "4.0")]));
+    check_prefixes(&squashfs);
+
+    let luks = b"LUKS\xBA\xBE\0\x02\0\0\0\0";
+    assert_eq!(mime(luks), "application/x-raw-disk-image");
+    assert_eq!(
+        params(luks),
+        pairs(&[("encryption", "luks"), ("version", "2")])
+    );
+    check_prefixes(luks);
+
+    // Text starting with the magic of a disk image is still text.
+    assert_eq!(mime(b"sqsh is a shell for sybase\n"), "text/plain");
+    assert_eq!(mime(b"KDMV notes for the team\n"), "text/plain");
+}
@@ -0,0 +1,45 @@
+// ELF headers, and no panic on any prefix of them.
+mod common;
+
+use common::{check_prefixes, mime, pairs, params};
+
+#[test]
+fn pie_executable() {
+    // A 64 bits PIE executable, with DF_1_PIE in its dynamic section.
+    let mut elf = vec![0; 240];
+    elf[..7].copy_from_slice(b"\x7FELF\x02\x01\x01");
+    elf[16] = 3;
+    elf[18] = 0x3E;
+    elf[20] = 1;
+    elf[32] = 64;
+    elf[52] = 64;
+    elf[54] = 56;
+    elf[56] = 2;
+    let ph = |t: u32, off: u64, size: u64| {
+        [
+            t.to_le_bytes().to_vec(),
+            vec![0; 4],
+            off.to_le_bytes().to_vec(),
+            vec![0; 16],
+            size.to_le_bytes().to_vec(),
+            vec![0; 16],
+        ]
+        .concat()
+    };
+    elf[64..120].copy_from_slice(&ph(3, 176, 28));
+    elf[120..176].copy_from_slice(&ph(2, 208, 32));
+    elf[176..204].copy_from_slice(b"/lib64/ld-linux-x86-64.so.2\0");
+    elf[208..216].copy_from_slice(&0x6FFFFFFBu64.to_le_bytes());
+    elf[216..224].copy_from_slice(&0x08000000u64.to_le_bytes());
+    assert_eq!(mime(&elf), "application/x-executable");
+    assert_eq!(
+        params(&elf),
+        pairs(&[
+            ("class", "64"),
+            ("endianness", "little"),
+            ("machine", "x86_64"),
+            ("abi", "sysv"),
+        ])
+    );
+    check_prefixes(&elf);
+}
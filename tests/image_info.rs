@@ -0,0 +1,85 @@
+// Image properties of each format, and no panic on any prefix of an image.
+mod common;
+
+use common::{check_prefixes, mime};
+use mimetype::image_info;
+
+#[test]
+fn jpeg() {
+    let mut tiff = b"Exif\0\0MM\0\x2A\0\0\0\x08\0\x01".to_vec();
+    // Orientation, SHORT, 1 value: 6.
+    tiff.extend_from_slice(&[0x01, 0x12, 0, 3, 0, 0, 0, 1, 0, 6, 0, 0, 0, 0, 0, 0]);
+    let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE1];
+    jpeg.extend_from_slice(&(tiff.len() as u16 + 2).to_be_bytes());
+    jpeg.extend_from_slice(&tiff);
+    // Baseline frame: 8 bits, 16x32, 3 components.
+    jpeg.extend_from_slice(&[0xFF, 0xC0, 0, 17, 8, 0, 16, 0, 32, 3]);
+    jpeg.extend_from_slice(&[1, 0x22, 0, 2, 0x11, 1, 3, 0x11, 1]);
+    jpeg.extend_from_slice(&[0xFF, 0xDA, 0, 2, 0xFF, 0xD9]);
+
+    assert_eq!(mime(&jpeg), "image/jpeg");
+    let info = image_info(&jpeg).unwrap();
+    assert_eq!((info.width, info.height), (32, 16));
+    assert_eq!(info.orientation, Some(6));
+    check_prefixes(&jpeg);
+
+    // Segment lengths count themselves, so are at least 2.
+    for len in [0, 1] {
+        let bad = [0xFF, 0xD8, 0xFF, 0xE0, 0, len, 0, 0, 0, 0];
+        assert!(image_info(&bad).is_none());
+    }
+}
+
+#[test]
+fn png() {
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    let mut chunk = |typ: &[u8], data: &[u8]| {
+        png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        png.extend_from_slice(typ);
+        png.extend_from_slice(data);
+        png.extend_from_slice(&[0; 4]);
+    };
+    chunk(b"IHDR", &[0, 0, 0, 3, 0, 0, 0, 2, 8, 6, 0, 0, 0]);
+    chunk(b"acTL", &[0, 0, 0, 2, 0, 0, 0, 0]);
+    chunk(b"IEND", &[]);
+
+    let info = image_info(&png).unwrap();
+    assert_eq!((info.width, info.height), (3, 2));
+    assert!(info.animated);
+    check_prefixes(&png);
+}
+
+#[test]
+fn gif_webp_bmp() {
+    let mut gif = b"GIF89a\x04\0\x02\0\x80\0\0".to_vec();
+    gif.extend_from_slice(&[0; 6]);
+    gif.extend_from_slice(b"\x21\xFF\x0BNETSCAPE2.0\x03\x01\0\0\0");
+    gif.extend_from_slice(b"\x2C\0\0\0\0\x04\0\x02\0\0\x02\x02\x44\x01\0\x3B");
+    let info = image_info(&gif).unwrap();
+    assert_eq!((info.width, info.height), (4, 2));
+    assert!(info.animated);
+    check_prefixes(&gif);
+
+    let mut vp8x = vec![0x12, 0, 0, 0];
+    vp8x.extend_from_slice(&[99, 0, 0, 49, 0, 0]);
+    let mut webp = b"RIFF\0\0\0\0WEBPVP8X".to_vec();
+    webp.extend_from_slice(&(vp8x.len() as u32).to_le_bytes());
+    webp.extend_from_slice(&vp8x);
+    let size = (webp.len() - 8) as u32;
+    webp[4..8].copy_from_slice(&size.to_le_bytes());
+    assert_eq!(mime(&webp), "image/webp");
+    let info = image_info(&webp).unwrap();
+    assert_eq!((info.width, info.height), (100, 50));
+    check_prefixes(&webp);
+
+    let mut bmp = b"BM".to_vec();
+    bmp.extend_from_slice(&[0; 12]);
+    bmp.extend_from_slice(&40u32.to_le_bytes());
+    bmp.extend_from_slice(&7i32.to_le_bytes());
+    bmp.extend_from_slice(&(-5i32).to_le_bytes());
+    bmp.extend_from_slice(&[1, 0, 24, 0]);
+    bmp.extend_from_slice(&[0; 24]);
+    let info = image_info(&bmp).unwrap();
+    assert_eq!((info.width, info.height), (7, 5));
+    check_prefixes(&bmp);
+}
@@ -0,0 +1,38 @@
+// ISO base media files, told apart by their brands and track handlers.
+mod common;
+
+use common::{bmff, check_prefixes, mime};
+use mimetype::image_info;
+
+#[test]
+fn iso_bmff() {
+    // An AVIF image: brands, then the item properties in the meta box.
+    let mut ispe = vec![0; 4];
+    ispe.extend_from_slice(&640u32.to_be_bytes());
+    ispe.extend_from_slice(&480u32.to_be_bytes());
+    let ipco = bmff(
+        b"ipco",
+        &[
+            bmff(b"ispe", &ispe),
+            bmff(b"pixi", &[0, 0, 0, 0, 3, 8, 8, 8]),
+        ]
+        .concat(),
+    );
+    let meta = bmff(b"meta", &[vec![0; 4], bmff(b"iprp", &ipco)].concat());
+    let avif = [bmff(b"ftyp", b"avif\0\0\0\0mif1avifmiaf"), meta].concat();
+    assert_eq!(mime(&avif), "image/avif");
+    let info = image_info(&avif).unwrap();
+    assert_eq!((info.width, info.height), (640, 480));
+    check_prefixes(&avif);
+
+    // An MP4 file with a single audio track.
+    let hdlr = bmff(b"hdlr", b"\0\0\0\0\0\0\0\0soun\0\0\0\0\0\0\0\0\0\0\0\0\0");
+    let trak = bmff(b"trak", &bmff(b"mdia", &hdlr));
+    let m4a = [bmff(b"ftyp", b"isom\0\0\0\0isommp41"), bmff(b"moov", &trak)].concat();
+    assert_eq!(mime(&m4a), "audio/mp4");
+    check_prefixes(&m4a);
+
+    let mov = bmff(b"ftyp", b"qt  \0\0\0\0qt  ");
+    assert_eq!(mime(&mov), "video/quicktime");
+    check_prefixes(&mov);
+}
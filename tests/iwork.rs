@@ -0,0 +1,25 @@
+// iWork documents, told apart by the contents of their zip archive.
+mod common;
+
+use common::{check_prefixes, mime, zip, ZipEntry};
+
+#[test]
+fn iwork() {
+    let keynote = zip(&[
+        ZipEntry::stored("Index/Document.iwa", b"\0\0\0\0"),
+        ZipEntry::stored("Index/Slide-1.iwa", b"\0"),
+        ZipEntry::stored("Index/Tables/Tile.iwa", b"\0"),
+    ]);
+    assert_eq!(mime(&keynote), "application/vnd.apple.keynote");
+    check_prefixes(&keynote);
+
+    // A Pages document embedding a table: its root object is a
+    // TP.DocumentArchive, of type 10000.
+    let iwa = b"\0\x0C\0\0\x0A\x24\x09\x08\x01\x12\x05\x08\x90\x4E\x12\0";
+    let pages = zip(&[
+        ZipEntry::stored("Index/Document.iwa", iwa),
+        ZipEntry::stored("Index/Tables/Tile.iwa", b"\0"),
+    ]);
+    assert_eq!(mime(&pages), "application/vnd.apple.pages");
+    check_prefixes(&pages);
+}
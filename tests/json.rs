@@ -0,0 +1,128 @@
+// JSON and NDJSON files, whole or cut short at the rate limit, and the JSON
+// vocabularies told apart by their root object.
+mod common;
+
+use common::mime;
+
+// long_array returns a JSON array of objects, longer than the rate limit.
+fn long_array() -> Vec<u8> {
+    let mut json = b"[".to_vec();
+    for i in 0..200 {
+        json.extend_from_slice(
+            format!("{{\"id\": {}, \"name\": \"user {}\"}},\n", i, i).as_bytes(),
+        );
+    }
+    json.extend_from_slice(b"{}]");
+    json
+}
+
+#[test]
+fn json() {
+    assert_eq!(mime(b"{\"a\": [1, 2, {\"b\": null}]}"), "application/json");
+    assert_eq!(mime(b"  [1, 2, 3]\n"), "application/json");
+    // Scalars are not reported as JSON.
+    assert_eq!(mime(b"\"hello\""), "text/plain");
+    assert_eq!(mime(b"12345"), "text/plain");
+    // A whole file is read, so a value ending early is not JSON.
+    assert_eq!(mime(b"{\"a\": [1, 2"), "text/plain");
+}
+
+#[test]
+fn truncated_json() {
+    // Cut at the rate limit in the middle of a value.
+    let json = long_array();
+    assert!(json.len() > 3072);
+    assert_eq!(mime(&json), "application/json");
+
+    // A syntax error before the limit is not excused by the truncation.
+    let mut bad = long_array();
+    let comma = bad.windows(2).position(|w| w == b",\n").unwrap();
+    bad[comma] = b'}';
+    assert_eq!(mime(&bad), "text/plain");
+}
+
+#[test]
+fn ndjson() {
+    let ndjson = b"{\"id\": 1}\n{\"id\": 2}\n{\"id\": 3}\n";
+    assert_eq!(mime(ndjson), "application/x-ndjson");
+    assert_eq!(mime(b"[1, 2]\r\n[3, 4]\r\n"), "application/x-ndjson");
+
+    // Values must be on lines of their own.
+    assert_eq!(mime(b"{\"id\": 1} {\"id\": 2}\n"), "text/plain");
+    // At least one of them an object or an array.
+    assert_eq!(mime(b"1\n2\n3\n"), "text/plain");
+    // A single value is plain JSON.
+    assert_eq!(mime(b"{\"id\": 1}\n"), "application/json");
+    // The last line may only be cut short by the rate limit.
+    assert_eq!(mime(b"{\"id\": 1}\n{\"id\": 2}\n{\"id\""), "text/plain");
+
+    let mut long = Vec::new();
+    for i in 0..200 {
+        long.extend_from_slice(format!("{{\"id\": {}, \"name\": \"user {}\"}}\n", i, i).as_bytes());
+    }
+    assert!(long.len() > 3072);
+    assert_eq!(mime(&long), "application/x-ndjson");
+}
+
+#[test]
+fn vocabularies() {
+    let cases: [(&str, &str); 11] = [
+        (
+            r#"{"type": "FeatureCollection", "features": []}"#,
+            "application/geo+json",
+        ),
+        (
+            r#"{"type": "Topology", "objects": {}}"#,
+            "application/topo+json",
+        ),
+        (
+            r#"{"$schema": "https://json-schema.org/draft/2020-12/schema"}"#,
+            "application/schema+json",
+        ),
+        (
+            r#"{"openapi": "3.1.0", "info": {}}"#,
+            "application/vnd.oai.openapi+json",
+        ),
+        (
+            r#"{"swagger": "2.0", "info": {}}"#,
+            "application/vnd.oai.openapi+json",
+        ),
+        (
+            r#"{"cells": [], "metadata": {}, "nbformat": 4}"#,
+            "application/x-ipynb+json",
+        ),
+        (r#"{"asset": {"version": "2.0"}}"#, "model/gltf+json"),
+        (
+            r#"{"manifest_version": 3, "name": "x", "version": "1.0"}"#,
+            "application/x-web-extension-manifest+json",
+        ),
+        (
+            r#"{"name": "App", "start_url": "/", "display": "standalone"}"#,
+            "application/manifest+json",
+        ),
+        (
+            r#"{"@context": "https://schema.org", "@type": "Person"}"#,
+            "application/ld+json",
+        ),
+        (
+            r#"{"@context": "https://www.w3.org/ns/activitystreams", "type": "Note"}"#,
+            "application/activity+json",
+        ),
+    ];
+    for (content, expected) in cases {
+        assert_eq!(mime(content.as_bytes()), expected, "{}", content);
+    }
+
+    // Keys of nested objects are not root keys.
+    assert_eq!(
+        mime(br#"{"data": {"type": "Topology"}}"#),
+        "application/json"
+    );
+    // An object cut short by the rate limit is told by the keys read so far.
+    let mut long = br#"{"openapi": "3.0.3", "paths": {"#.to_vec();
+    for i in 0..200 {
+        long.extend_from_slice(format!("\"/item/{}\": {{}},\n", i).as_bytes());
+    }
+    long.extend_from_slice(b"\"/\": {}}}");
+    assert_eq!(mime(&long), "application/vnd.oai.openapi+json");
+}
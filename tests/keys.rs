@@ -0,0 +1,123 @@
+// PEM, PGP, SSH and PuTTY key material, and the private key check.
+mod common;
+
+use common::mime;
+use mimetype::contains_private_key;
+
+// pem returns a PEM block of label, with headers before the body.
+fn pem(label: &str, headers: &str, body: &str) -> Vec<u8> {
+    format!(
+        "-----BEGIN {}-----\n{}{}\n-----END {}-----\n",
+        label, headers, body, label
+    )
+    .into_bytes()
+}
+
+#[test]
+fn pem_labels() {
+    let cases = [
+        ("CERTIFICATE", "application/x-x509-ca-cert"),
+        ("CERTIFICATE REQUEST", "application/pkcs10"),
+        ("X509 CRL", "application/pkix-crl"),
+        ("PRIVATE KEY", "application/pkcs8"),
+        ("ENCRYPTED PRIVATE KEY", "application/pkcs8-encrypted"),
+        ("RSA PRIVATE KEY", "application/x-rsa-private-key"),
+        ("EC PRIVATE KEY", "application/x-ec-private-key"),
+        ("DSA PRIVATE KEY", "application/x-dsa-private-key"),
+        ("PGP MESSAGE", "application/pgp-encrypted"),
+        ("PGP PUBLIC KEY BLOCK", "application/pgp-keys"),
+        ("PGP PRIVATE KEY BLOCK", "application/x-pgp-private-key"),
+        ("PGP SIGNATURE", "application/pgp-signature"),
+        ("DH PARAMETERS", "application/x-pem-file"),
+    ];
+    for (label, expected) in cases {
+        assert_eq!(mime(&pem(label, "", "MIIB")), expected, "{}", label);
+    }
+
+    // OpenSSL writes the parameters of an EC key before it.
+    let key = [
+        pem("EC PARAMETERS", "", "BggqhkjOPQMBBw=="),
+        pem("EC PRIVATE KEY", "", "MHcC"),
+    ]
+    .concat();
+    assert_eq!(mime(&key), "application/x-ec-private-key");
+}
+
+#[test]
+fn encrypted_keys() {
+    let headers = "Proc-Type: 4,ENCRYPTED\nDEK-Info: AES-128-CBC,00\n\n";
+    assert_eq!(
+        mime(&pem("RSA PRIVATE KEY", headers, "MIIE")),
+        "application/x-rsa-private-key-encrypted"
+    );
+    assert_eq!(
+        mime(&pem("EC PRIVATE KEY", headers, "MHcC")),
+        "application/x-ec-private-key-encrypted"
+    );
+
+    // The cipher name follows the magic of OpenSSH keys.
+    let openssh = pem(
+        "OPENSSH PRIVATE KEY",
+        "",
+        "b3BlbnNzaC1rZXktdjEAAAAABG5vbmUAAAAEbm9uZQ",
+    );
+    assert_eq!(mime(&openssh), "application/x-openssh-private-key");
+    let openssh = pem(
+        "OPENSSH PRIVATE KEY",
+        "",
+        "b3BlbnNzaC1rZXktdjEAAAAACmFlczI1Ni1jdHI",
+    );
+    assert_eq!(
+        mime(&openssh),
+        "application/x-openssh-private-key-encrypted"
+    );
+}
+
+#[test]
+fn ssh_and_putty() {
+    let public = b"ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIOMq alice@host\n";
+    assert_eq!(mime(public), "application/x-ssh-public-key");
+    let public = b"---- BEGIN SSH2 PUBLIC KEY ----\nComment: alice\nAAAAB3Nza\n";
+    assert_eq!(mime(public), "application/x-ssh-public-key");
+    assert_eq!(mime(b"ssh-rsa is an algorithm\n"), "text/plain");
+
+    let ppk = b"PuTTY-User-Key-File-3: ssh-ed25519\nEncryption: none\nComment: k\n";
+    assert_eq!(mime(ppk), "application/x-putty-private-key");
+    let ppk = b"PuTTY-User-Key-File-3: ssh-ed25519\nEncryption: aes256-cbc\nComment: k\n";
+    assert_eq!(mime(ppk), "application/x-putty-private-key-encrypted");
+}
+
+#[test]
+fn private_keys() {
+    // Anywhere in the content, encrypted or not.
+    let bundle = [
+        pem("CERTIFICATE", "", "MIIB"),
+        pem("ENCRYPTED PRIVATE KEY", "", "MIIF"),
+    ]
+    .concat();
+    assert_eq!(mime(&bundle), "application/x-x509-ca-cert");
+    assert!(contains_private_key(&bundle));
+    for label in [
+        "PRIVATE KEY",
+        "RSA PRIVATE KEY",
+        "OPENSSH PRIVATE KEY",
+        "PGP PRIVATE KEY BLOCK",
+    ] {
+        assert!(contains_private_key(&pem(label, "", "MIIB")), "{}", label);
+    }
+    assert!(contains_private_key(
+        b"PuTTY-User-Key-File-2: ssh-rsa\nEncryption: none\n"
+    ));
+
+    for label in [
+        "CERTIFICATE",
+        "PUBLIC KEY",
+        "PGP PUBLIC KEY BLOCK",
+        "PGP SIGNATURE",
+    ] {
+        assert!(!contains_private_key(&pem(label, "", "MIIB")), "{}", label);
+    }
+    assert!(!contains_private_key(
+        b"ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIOMq alice@host\n"
+    ));
+}
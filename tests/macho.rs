@@ -0,0 +1,31 @@
+// Mach-O universal and thin binaries, and the Java class files sharing
+// their magic.
+mod common;
+
+use common::{check_prefixes, mime, pairs, params};
+
+#[test]
+fn universal_and_thin() {
+    let mut fat = b"\xCA\xFE\xBA\xBE\0\0\0\x02".to_vec();
+    for (cpu, offset) in [(0x01000007u32, 0x1000u32), (0x0100000C, 0x2000)] {
+        for v in [cpu, 3, offset, 0x100, 12] {
+            fat.extend_from_slice(&v.to_be_bytes());
+        }
+    }
+    assert_eq!(mime(&fat), "application/x-mach-binary");
+    assert_eq!(params(&fat), pairs(&[("arch", "x86_64, arm64")]));
+    check_prefixes(&fat);
+
+    let mut thin = b"\xCF\xFA\xED\xFE".to_vec();
+    for v in [0x0100000Cu32, 0, 2, 0, 0, 0, 0] {
+        thin.extend_from_slice(&v.to_le_bytes());
+    }
+    assert_eq!(mime(&thin), "application/x-mach-o-executable");
+    assert_eq!(params(&thin), pairs(&[("arch", "arm64")]));
+    check_prefixes(&thin);
+
+    // A Java class file shares the magic of universal binaries.
+    let class = b"\xCA\xFE\xBA\xBE\0\0\0\x3D\0\x10\x0A\0\x02";
+    assert_eq!(mime(class), "application/x-java-applet");
+    check_prefixes(class);
+}
@@ -0,0 +1,29 @@
+// Mail messages and mailboxes, and the "key: value" files they look like.
+mod common;
+
+use common::mime;
+
+#[test]
+fn messages() {
+    let eml = b"From: Alice <alice@example.com>\r\nTo: bob@example.com\r\n\
+                Subject: Lunch\r\nDate: Mon, 1 Jan 2024 10:20:30 +0000\r\n\r\nNoon?\r\n";
+    assert_eq!(mime(eml), "message/rfc822");
+
+    // Folded header lines belong to the field above them.
+    let eml = b"Received: from mx.example.com\n\tby mail.example.org\nSubject: Lunch\n\
+                Date: Mon, 1 Jan 2024 10:20:30 +0000\n\nNoon?\n";
+    assert_eq!(mime(eml), "message/rfc822");
+
+    let mbox = b"From alice@example.com Mon Jan  1 10:20:30 2024\n\
+                 From: alice@example.com\nTo: bob@example.com\nSubject: Lunch\n\nNoon?\n";
+    assert_eq!(mime(mbox), "application/mbox");
+}
+
+#[test]
+fn yaml_with_mail_keys() {
+    // Mail field names, but neither addresses nor a sequence in a message.
+    let yaml = b"to: alice\nfrom: bob\ndate: 2024-01-01\nsubject: hi\nitems:\n  - a\n  - b\n";
+    assert_eq!(mime(yaml), "application/yaml");
+    let yaml = b"to: alice\nfrom: bob\ndate: 2024-01-01\nsubject: hi\n";
+    assert_ne!(mime(yaml), "message/rfc822");
+}
@@ -0,0 +1,62 @@
+// YAML, TOML, INI, properties and Markdown files, and the text files looking
+// like them.
+mod common;
+
+use common::mime;
+
+#[test]
+fn yaml() {
+    let yaml = b"# service\nname: app\nversion: 1.0\ndeps:\n  - a\n  - b\n";
+    assert_eq!(mime(yaml), "application/yaml");
+    assert_eq!(mime(b"%YAML 1.2\n---\nfoo\n"), "application/yaml");
+    assert_eq!(mime(b"---\ntitle: Post\n...\n"), "application/yaml");
+    // Block scalars hold any text.
+    let yaml = b"script: |\n  echo [this] = that\n  exit 1\nimage: alpine\n";
+    assert_eq!(mime(yaml), "application/yaml");
+
+    // Tabs are not YAML indentation, nor is a single mapping enough.
+    assert_eq!(mime(b"a: 1\nb:\n\t- c\n"), "text/plain");
+    assert_eq!(mime(b"Note: call back later\n"), "text/plain");
+}
+
+#[test]
+fn toml() {
+    let toml = b"[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[dependencies]\nserde = { version = \"1\" }\n";
+    assert_eq!(mime(toml), "application/toml");
+    let toml =
+        b"title = \"Example\"\ndate = 1979-05-27T07:32:00Z\nports = [8000, 8001]\nenabled = true\n";
+    assert_eq!(mime(toml), "application/toml");
+}
+
+#[test]
+fn ini() {
+    let ini = b"; settings\n[server]\nhost=localhost\nport=8080\n\n[client]\nretries=3\n";
+    assert_eq!(mime(ini), "text/x-ini");
+    // Values which are not TOML values make an INI file of TOML-like syntax.
+    let ini = b"[server]\nhost = localhost\n";
+    assert_eq!(mime(ini), "text/x-ini");
+}
+
+#[test]
+fn properties() {
+    let properties =
+        b"# app\napp.name=Example\napp.greeting: Hello \\\n    world\nserver.port=8080\n";
+    assert_eq!(mime(properties), "text/x-java-properties");
+    // Keys have no spaces.
+    assert_eq!(mime(b"my key=1\nother key=2\n"), "text/plain");
+}
+
+#[test]
+fn markdown() {
+    let md = b"# Title\n\nSome **bold** text and a [link](https://example.com).\n\n- one\n- two\n";
+    assert_eq!(mime(md), "text/markdown");
+    let md = b"Title\n=====\n\n```rust\nfn main() {}\n```\n";
+    assert_eq!(mime(md), "text/markdown");
+
+    // A heading alone, or a list alone, is not enough.
+    assert_eq!(
+        mime(b"# not a heading in a script\necho hi\n"),
+        "text/plain"
+    );
+    assert_eq!(mime(b"Shopping:\n- milk\n- eggs\n"), "text/plain");
+}
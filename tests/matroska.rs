@@ -0,0 +1,26 @@
+// Matroska and WebM files, told apart by their EBML header and tracks.
+mod common;
+
+use common::{check_prefixes, mime};
+
+// ebml returns an EBML element with a one byte size.
+fn ebml(id: &[u8], data: &[u8]) -> Vec<u8> {
+    [id, &[0x80 | data.len() as u8], data].concat()
+}
+
+#[test]
+fn webm() {
+    let header = ebml(
+        b"\x1A\x45\xDF\xA3",
+        &[ebml(b"\x42\x82", b"webm"), ebml(b"\x42\x87", &[4])].concat(),
+    );
+    let tracks = ebml(b"\x16\x54\xAE\x6B", &ebml(b"\xAE", &ebml(b"\x83", &[2])));
+    let segment = [
+        b"\x18\x53\x80\x67\x01\xFF\xFF\xFF\xFF\xFF\xFF\xFF".to_vec(),
+        tracks,
+    ]
+    .concat();
+    let webm = [header, segment].concat();
+    assert_eq!(mime(&webm), "audio/webm");
+    check_prefixes(&webm);
+}
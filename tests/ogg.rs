@@ -0,0 +1,15 @@
+// Ogg streams, and the codecs of their logical streams.
+mod common;
+
+use common::{check_prefixes, mime, pairs, params};
+
+#[test]
+fn opus() {
+    let mut ogg = b"OggS\0\x02".to_vec();
+    ogg.extend_from_slice(&[0; 20]);
+    ogg.extend_from_slice(&[1, 19]);
+    ogg.extend_from_slice(b"OpusHead\x01\x02\x38\x01\x80\xBB\0\0\0\0\0");
+    assert_eq!(mime(&ogg), "audio/opus");
+    assert_eq!(params(&ogg), pairs(&[("codecs", "opus")]));
+    check_prefixes(&ogg);
+}
@@ -0,0 +1,62 @@
+// PE and DOS executables, and no panic on any prefix of their headers.
+mod common;
+
+use common::{check_prefixes, mime, pairs, params};
+
+// pe returns a PE32+ .NET library whose CLR header points to a ReadyToRun
+// header.
+fn pe() -> Vec<u8> {
+    let mut pe = vec![0; 0x400];
+    pe[..2].copy_from_slice(b"MZ");
+    pe[0x18] = 0x40;
+    pe[0x3C] = 0x80;
+    pe[0x80..0x84].copy_from_slice(b"PE\0\0");
+    let coff = 0x84;
+    pe[coff..coff + 2].copy_from_slice(&0x8664u16.to_le_bytes());
+    pe[coff + 2] = 1;
+    pe[coff + 16..coff + 18].copy_from_slice(&240u16.to_le_bytes());
+    pe[coff + 18..coff + 20].copy_from_slice(&0x2022u16.to_le_bytes());
+    let opt = coff + 20;
+    pe[opt..opt + 2].copy_from_slice(&0x20Bu16.to_le_bytes());
+    pe[opt + 68] = 3;
+    // The CLR runtime header directory.
+    let clr = opt + 112 + 14 * 8;
+    pe[clr..clr + 4].copy_from_slice(&0x2000u32.to_le_bytes());
+    pe[clr + 4] = 72;
+    // A section mapping 0x2000 to 0x200.
+    let section = opt + 240;
+    pe[section..section + 5].copy_from_slice(b".text");
+    pe[section + 8..section + 12].copy_from_slice(&0x1000u32.to_le_bytes());
+    pe[section + 12..section + 16].copy_from_slice(&0x2000u32.to_le_bytes());
+    pe[section + 16..section + 20].copy_from_slice(&0x200u32.to_le_bytes());
+    pe[section + 20..section + 24].copy_from_slice(&0x200u32.to_le_bytes());
+    // ManagedNativeHeader, then the ReadyToRun header.
+    pe[0x240..0x244].copy_from_slice(&0x2100u32.to_le_bytes());
+    pe[0x300..0x308].copy_from_slice(b"RTR\0\x09\0\x02\0");
+    pe
+}
+
+#[test]
+fn pe_and_dos() {
+    let pe = pe();
+    assert_eq!(mime(&pe), "application/vnd.microsoft.portable-executable");
+    assert_eq!(
+        params(&pe),
+        pairs(&[
+            ("kind", "dll"),
+            ("machine", "x64"),
+            ("subsystem", "console"),
+            ("runtime", "clr"),
+            ("readytorun", "9.2"),
+        ])
+    );
+    check_prefixes(&pe);
+
+    // A DOS program with garbage at the new header offset.
+    let mut dos = vec![0; 256];
+    dos[..2].copy_from_slice(b"MZ");
+    dos[0x18] = 0x1C;
+    dos[0x3C..0x40].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert_eq!(mime(&dos), "application/x-dosexec");
+    check_prefixes(&dos);
+}
@@ -0,0 +1,16 @@
+// RIFF containers, and the codec of WAV files.
+mod common;
+
+use common::{check_prefixes, mime, pairs, params};
+
+#[test]
+fn wav() {
+    let mut wav = b"RIFF\0\0\0\0WAVEfmt \x10\0\0\0".to_vec();
+    wav.extend_from_slice(&[1, 0, 2, 0, 0x44, 0xAC, 0, 0, 0x10, 0xB1, 2, 0, 4, 0, 16, 0]);
+    wav.extend_from_slice(b"data\x04\0\0\0\0\0\0\0");
+    let size = (wav.len() - 8) as u32;
+    wav[4..8].copy_from_slice(&size.to_le_bytes());
+    assert_eq!(mime(&wav), "audio/wav");
+    assert_eq!(params(&wav), pairs(&[("codec", "pcm")]));
+    check_prefixes(&wav);
+}
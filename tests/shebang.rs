@@ -0,0 +1,61 @@
+// Scripts told apart by the interpreter of their shebang line.
+mod common;
+
+use common::mime;
+
+#[test]
+fn interpreters() {
+    let cases: [(&[u8], &str); 16] = [
+        (b"#!/bin/sh\necho hi\n", "text/x-shellscript"),
+        (b"#!/usr/bin/zsh\n", "text/x-shellscript"),
+        (b"#!/bin/tcsh\n", "application/x-csh"),
+        (b"#!/usr/bin/fish\n", "text/x-fish"),
+        (b"#!/usr/bin/node\n", "application/javascript"),
+        (b"#!/usr/bin/lua\n", "text/x-lua"),
+        (b"#!/usr/bin/perl -w\n", "text/x-perl"),
+        (b"#!/usr/bin/python\n", "text/x-python"),
+        (b"#!/usr/bin/ruby\n", "text/x-ruby"),
+        (b"#!/usr/bin/tclsh\n", "text/x-tcl"),
+        (b"#!/usr/bin/awk -f\n", "text/x-awk"),
+        (b"#!/usr/bin/pwsh\n", "text/x-powershell"),
+        (b"#!/usr/bin/groovy\n", "text/x-groovy"),
+        (b"#!/usr/bin/Rscript\n", "text/x-r"),
+        (b"#!/usr/bin/julia\n", "text/x-julia"),
+        (b"#!/usr/bin/elixir\n", "text/x-elixir"),
+    ];
+    for (content, expected) in cases {
+        assert_eq!(
+            mime(content),
+            expected,
+            "{}",
+            String::from_utf8_lossy(content)
+        );
+    }
+}
+
+#[test]
+fn env() {
+    assert_eq!(mime(b"#!/usr/bin/env python\n"), "text/x-python");
+    assert_eq!(mime(b"#!/usr/bin/env bash\n"), "text/x-shellscript");
+    // Options and variable assignments come before the command.
+    assert_eq!(mime(b"#!/usr/bin/env -i PATH=/bin ruby\n"), "text/x-ruby");
+    assert_eq!(
+        mime(b"#!/usr/bin/env -u HOME node\n"),
+        "application/javascript"
+    );
+    // -S splits the rest of the line into the command and its arguments.
+    assert_eq!(mime(b"#!/usr/bin/env -S python3 -u\n"), "text/x-python");
+    assert_eq!(mime(b"#!/usr/bin/env -Sperl -w\n"), "text/x-perl");
+    // Nothing to run.
+    assert_eq!(mime(b"#!/usr/bin/env -i\n"), "text/plain");
+}
+
+#[test]
+fn version_suffix() {
+    assert_eq!(mime(b"#!/usr/bin/python3\n"), "text/x-python");
+    assert_eq!(mime(b"#!/usr/bin/python3.11\n"), "text/x-python");
+    assert_eq!(mime(b"#!/usr/bin/env lua5.4\n"), "text/x-lua");
+    // A version alone is no interpreter.
+    assert_eq!(mime(b"#!/opt/3.11\n"), "text/plain");
+    assert_eq!(mime(b"#!/usr/bin/pythonx\n"), "text/plain");
+}
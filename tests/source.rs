@@ -0,0 +1,102 @@
+// The source code classifier, and the threshold it is reported by detect at.
+use mimetype::{detect, detect_source_language, set_source_threshold, Language};
+
+const SAMPLES: [(Language, &[u8]); 11] = [
+    (
+        Language::C,
+        b"#include <stdio.h>\n#include <stdlib.h>\n\nint main(int argc, char **argv) {\n    char *buf = malloc(sizeof(int) * 4);\n    printf(\"%s\\n\", argv[0]);\n    return 0;\n}\n",
+    ),
+    (
+        Language::Cpp,
+        b"#include <iostream>\n#include <vector>\n\nusing namespace std;\n\nint main() {\n    std::vector<int> v;\n    cout << v.size() << endl;\n}\n",
+    ),
+    (
+        Language::Rust,
+        b"use std::fs;\n\n#[derive(Debug)]\npub struct Config {\n    name: String,\n}\n\nfn main() {\n    let mut c = Config { name: String::new() };\n    println!(\"{:?}\", c);\n}\n",
+    ),
+    (
+        Language::Go,
+        b"package main\n\nimport (\n\t\"fmt\"\n\t\"os\"\n)\n\nfunc main() {\n\tf, err := os.Open(\"x\")\n\tif err != nil {\n\t\tfmt.Println(err)\n\t}\n\tdefer f.Close()\n}\n",
+    ),
+    (
+        Language::Java,
+        b"import java.util.List;\n\npublic class Main {\n    public static void main(String[] args) {\n        System.out.println(\"hi\");\n    }\n}\n",
+    ),
+    (
+        Language::CSharp,
+        b"using System;\n\nnamespace App\n{\n    class Program\n    {\n        static void Main(string[] args)\n        {\n            Console.WriteLine(\"hi\");\n        }\n    }\n}\n",
+    ),
+    (
+        Language::TypeScript,
+        b"export interface User {\n  name: string;\n  age: number;\n}\n\nexport function greet(u: User): void {\n  const ok: boolean = true;\n}\n",
+    ),
+    (
+        Language::Sql,
+        b"CREATE TABLE users (\n  id INTEGER PRIMARY KEY,\n  name VARCHAR(64)\n);\nINSERT INTO users VALUES (1, 'a');\nSELECT name FROM users WHERE id = 1 ORDER BY name;\n",
+    ),
+    (
+        Language::Shell,
+        b"set -e\nif [ -z \"$1\" ]; then\n  echo \"usage\"\n  exit 1\nfi\nfor f in $(ls); do\n  echo \"$f\"\ndone\n",
+    ),
+    (
+        Language::Makefile,
+        b"CC ?= gcc\n\n.PHONY: all clean\n\nall: main\n\nmain: main.o\n\t$(CC) -o $@ $^\n\nclean:\n\trm -f main main.o\n",
+    ),
+    (
+        Language::Dockerfile,
+        b"FROM debian:12\nWORKDIR /app\nCOPY . .\nRUN make\nEXPOSE 8080\nCMD [\"./app\"]\n",
+    ),
+];
+
+#[test]
+fn languages() {
+    for (language, content) in SAMPLES {
+        let (classified, confidence) = detect_source_language(content).unwrap();
+        assert_eq!(classified, language);
+        assert!(confidence > 0.75 && confidence <= 1.0, "{:?}", language);
+    }
+    assert_eq!(
+        detect_source_language(b"The quick brown fox jumps over the lazy dog.\n"),
+        None
+    );
+}
+
+#[test]
+fn confidence() {
+    // Only Shell features: the best score is the total, past saturation.
+    let (_, confidence) = detect_source_language(SAMPLES[8].1).unwrap();
+    assert_eq!(confidence, 1.0);
+
+    // Two Rust functions score 4, a third of the saturation score.
+    let (language, confidence) = detect_source_language(b"fn a() {}\nfn b() {}\n").unwrap();
+    assert_eq!(language, Language::Rust);
+    assert!((confidence - 1.0 / 3.0).abs() < 1e-6);
+
+    // A single one is below the minimum score.
+    assert_eq!(detect_source_language(b"fn a() {}\n"), None);
+}
+
+// The threshold is global, so it is only changed by this test.
+#[test]
+fn threshold() {
+    let (_, rust) = SAMPLES[2];
+    let weak = b"fn a() {}\nfn b() {}\n";
+
+    // The classifier is disabled by default.
+    assert_eq!(detect(rust).mime, "text/plain");
+
+    set_source_threshold(0.3);
+    assert_eq!(detect(rust).mime, "text/x-rust");
+    assert_eq!(detect(rust).extension, ".rs");
+    assert_eq!(detect(weak).mime, "text/x-rust");
+    for (language, content) in SAMPLES {
+        assert_eq!(detect(content).mime, language.mime());
+    }
+
+    set_source_threshold(0.5);
+    assert_eq!(detect(rust).mime, "text/x-rust");
+    assert_eq!(detect(weak).mime, "text/plain");
+
+    set_source_threshold(0.0);
+    assert_eq!(detect(rust).mime, "text/plain");
+}
@@ -0,0 +1,56 @@
+// Subtitle formats, and the text files looking like them.
+mod common;
+
+use common::mime;
+
+#[test]
+fn webvtt() {
+    assert_eq!(
+        mime(b"WEBVTT\n\n00:00.000 --> 00:01.000\nHello\n"),
+        "text/vtt"
+    );
+    assert_eq!(mime(b"\xEF\xBB\xBFWEBVTT - Title\r\n\r\n"), "text/vtt");
+    assert_eq!(mime(b"WEBVTTX\n"), "text/plain");
+}
+
+#[test]
+fn subrip() {
+    let srt =
+        b"1\n00:00:01,000 --> 00:00:02,500\nHello\n\n2\n00:00:03,000 --> 00:00:04,000\nWorld\n";
+    assert_eq!(mime(srt), "application/x-subrip");
+    // A period for the fraction, and display coordinates after the end.
+    let srt = b"1\r\n00:00:01.000 --> 00:00:02.500 X1:10 X2:20\r\nHello\r\n";
+    assert_eq!(mime(srt), "application/x-subrip");
+
+    // Cues end after they start, and hold some text.
+    assert_eq!(
+        mime(b"1\n00:00:02,000 --> 00:00:01,000\nHello\n"),
+        "text/plain"
+    );
+    assert_eq!(mime(b"1\n00:00:01,000 --> 00:00:02,000\n\n"), "text/plain");
+    assert_eq!(
+        mime(b"1\n00:00:61,000 --> 00:01:02,000\nHello\n"),
+        "text/plain"
+    );
+}
+
+#[test]
+fn substation_alpha() {
+    let ssa = b"[Script Info]\nTitle: Example\nScriptType: v4.00\n\n[V4 Styles]\n";
+    assert_eq!(mime(ssa), "text/x-ssa");
+    let ass = b"[Script Info]\nTitle: Example\nScriptType: v4.00+\n";
+    assert_eq!(mime(ass), "text/x-ass");
+    let ass = b"[Script Info]\nTitle: Example\n\n[V4+ Styles]\n";
+    assert_eq!(mime(ass), "text/x-ass");
+}
+
+#[test]
+fn markup() {
+    let ttml = b"<?xml version=\"1.0\"?>\n<tt xmlns=\"http://www.w3.org/ns/ttml\">\n";
+    assert_eq!(mime(ttml), "application/ttml+xml");
+    let dfxp = b"<?xml version=\"1.0\"?>\n<tt xmlns=\"http://www.w3.org/2006/10/ttaf1\">\n";
+    assert_eq!(mime(dfxp), "application/ttml+xml");
+
+    let sami = b"<SAMI>\n<HEAD><TITLE>Example</TITLE></HEAD>\n";
+    assert_eq!(mime(sami), "application/x-sami");
+}
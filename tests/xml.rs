@@ -0,0 +1,66 @@
+// XML vocabularies, told apart by their root element and namespace.
+mod common;
+
+use common::mime;
+
+#[test]
+fn vocabularies() {
+    let cases: [(&str, &str); 13] = [
+        (r#"<rss version="2.0">"#, "application/rss+xml"),
+        (
+            r#"<feed xmlns="http://www.w3.org/2005/Atom">"#,
+            "application/atom+xml",
+        ),
+        (
+            r#"<X3D xmlns:xsd="http://www.w3.org/2001/XMLSchema-instance">"#,
+            "model/x3d+xml",
+        ),
+        (
+            r#"<kml xmlns="http://www.opengis.net/kml/2.2">"#,
+            "application/vnd.google-earth.kml+xml",
+        ),
+        (
+            r#"<xliff xmlns="urn:oasis:names:tc:xliff:document:2.0">"#,
+            "application/x-xliff+xml",
+        ),
+        (
+            r#"<COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema">"#,
+            "model/vnd.collada+xml",
+        ),
+        (
+            r#"<FeatureCollection xmlns:gml="http://www.opengis.net/gml">"#,
+            "application/gml+xml",
+        ),
+        (
+            r#"<gpx xmlns="http://www.topografix.com/GPX/1/1">"#,
+            "application/gpx+xml",
+        ),
+        (
+            r#"<TrainingCenterDatabase xmlns="http://www.garmin.com/xmlschemas/TrainingCenterDatabase/v2">"#,
+            "application/vnd.garmin.tcx+xml",
+        ),
+        (r#"<amf unit="millimeter">"#, "application/x-amf"),
+        (
+            r#"<model xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">"#,
+            "application/vnd.ms-package.3dmanufacturing-3dmodel+xml",
+        ),
+        (
+            r#"<xfdf xmlns="http://ns.adobe.com/xfdf/">"#,
+            "application/vnd.adobe.xfdf",
+        ),
+        ("<USFSubtitles>", "application/x-usf"),
+    ];
+    for (root, expected) in cases {
+        let content = format!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n{}\n", root);
+        assert_eq!(mime(content.as_bytes()), expected, "{}", root);
+    }
+}
+
+#[test]
+fn generic_xml() {
+    assert_eq!(mime(b"<?xml version=\"1.0\"?>\n<catalog>\n"), "text/xml");
+    // The namespace must follow the root element it qualifies.
+    let content = b"<?xml version=\"1.0\"?>\n<catalog>\n\
+                    <!-- xmlns=\"http://www.w3.org/2005/Atom\" -->\n<feed>\n";
+    assert_eq!(mime(content), "text/xml");
+}